@@ -1,38 +1,136 @@
 pub use std::fmt::Debug;
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Deref, DerefMut},
+};
 
 pub trait HeapVal: Ord + Debug {}
 impl<T: Ord + Debug> HeapVal for T {}
 
 pub type NodeIdx = usize;
 
-/// Binary min-heap implementation
-#[derive(Debug, Default)]
-pub struct BinaryHeap<T: HeapVal> {
+/// Ordering of the elements in a heap, the smallest element according to `compare` is at the top
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Smallest element first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinOrder;
+
+impl<T: Ord> Compare<T> for MinOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Largest element first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxOrder;
+
+impl<T: Ord> Compare<T> for MaxOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Element with the smallest key first, the key being extracted by a function
+#[derive(Debug, Clone, Copy)]
+pub struct KeyOrder<F>(pub F);
+
+impl<T, K: Ord, F: Fn(&T) -> K> Compare<T> for KeyOrder<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// Binary heap implementation, a min-heap by default
+pub struct BinaryHeap<T, C = MinOrder> {
     pub nodes: Vec<T>,
+    cmp: C,
+}
+
+impl<T: Debug, C: Compare<T>> Debug for BinaryHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BinaryHeap")
+            .field("nodes", &self.nodes)
+            .finish()
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for BinaryHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
 }
 
 impl<T: HeapVal> BinaryHeap<T> {
     pub const fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            cmp: MinOrder,
+        }
     }
 
-    pub fn len(&self) -> usize {
-        self.nodes.len()
+    pub const fn new_min() -> Self {
+        Self::new()
     }
 
-    pub fn is_empty(&self) -> bool {
-        self.nodes.is_empty()
+    pub fn from_vec(nodes: Vec<T>) -> Self {
+        Self::from_vec_with(nodes, MinOrder)
     }
+}
 
-    pub fn from_vec(nodes: Vec<T>) -> Self {
-        let mut heap = Self::new();
-        heap.nodes = nodes;
-        for i in (0..heap.nodes.len()).rev() {
-            heap.heapify_down(i);
+impl<T: HeapVal> BinaryHeap<T, MaxOrder> {
+    pub const fn new_max() -> Self {
+        Self {
+            nodes: Vec::new(),
+            cmp: MaxOrder,
+        }
+    }
+}
+
+impl<T, F> BinaryHeap<T, KeyOrder<F>> {
+    /// Heap ordered by the key returned by `key`, smallest key first
+    pub fn with_key<K: Ord>(key: F) -> Self
+    where
+        F: Fn(&T) -> K,
+    {
+        Self::with_comparator(KeyOrder(key))
+    }
+}
+
+impl<T, C: Compare<T>> BinaryHeap<T, C> {
+    /// Heap ordered by `cmp`, the element comparing as `Less` than all the others is at the top
+    pub fn with_comparator(cmp: C) -> Self {
+        Self {
+            nodes: Vec::new(),
+            cmp,
         }
+    }
+
+    /// Build a heap from unordered elements in O(n)
+    pub fn from_vec_with(nodes: Vec<T>, cmp: C) -> Self {
+        let mut heap = Self { nodes, cmp };
+        heapify(&mut heap.nodes, 2, &heap.cmp);
         heap
     }
 
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
     pub fn push(&mut self, val: T) {
         let new_node_id = self.nodes.len();
         self.nodes.push(val);
@@ -43,12 +141,39 @@ impl<T: HeapVal> BinaryHeap<T> {
         if self.nodes.is_empty() {
             return None;
         }
-        // pop root, the left child automatically becomes the new root
-        let popped = self.nodes.remove(0);
+        // move the root to the end so that it can be popped in O(1),
+        // the last leaf becomes the new root and sinks down to its place
+        let last = self.nodes.len() - 1;
+        self.nodes.swap(0, last);
+        let popped = self.nodes.pop();
         if !self.nodes.is_empty() {
             self.heapify_down(0);
         }
-        Some(popped)
+        popped
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.nodes.first()
+    }
+
+    /// Mutable access to the top element
+    ///
+    /// The heap is fixed when the returned guard is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.nodes.is_empty() {
+            None
+        } else {
+            Some(PeekMut { heap: self })
+        }
+    }
+
+    /// Consume the heap, returning its elements in the order they would be popped
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(val) = self.pop() {
+            sorted.push(val);
+        }
+        sorted
     }
 
     #[inline]
@@ -74,52 +199,124 @@ impl<T: HeapVal> BinaryHeap<T> {
         )
     }
 
-    pub fn heapify_up(&mut self, mut ni: NodeIdx) {
-        // while we have a parent and we are larger than him
-        while ni != 0 && self.nodes[self.parent(ni)] > self.nodes[ni] {
-            let parent_id = self.parent(ni);
-            self.nodes.swap(ni, parent_id);
-            ni = parent_id;
-        }
+    pub fn heapify_up(&mut self, ni: NodeIdx) {
+        sift_up(&mut self.nodes, ni, 2, &self.cmp);
     }
 
-    pub fn heapify_down(&mut self, mut ni: NodeIdx) {
-        loop {
-            let (left, right) = self.children(ni);
-            let mut largest = ni;
+    pub fn heapify_down(&mut self, ni: NodeIdx) {
+        sift_down(&mut self.nodes, ni, 2, &self.cmp);
+    }
 
-            // for a min-heap we swap with the largest child
+    pub fn iter(&'_ self) -> std::slice::Iter<'_, T> {
+        self.nodes.iter()
+    }
+}
 
-            // if the left child is larger than the current node
-            if let Some(left) = left {
-                if self.nodes[left] < self.nodes[largest] {
-                    largest = left;
-                }
-            }
-            // if the right child is larger than the current node
-            if let Some(right) = right {
-                if self.nodes[right] < self.nodes[largest] {
-                    largest = right;
-                }
-            }
+// The sifting logic works on a plain slice so that it can be shared by heaps of any arity,
+// and by algorithms such as heap sort that lay out a heap in a borrowed slice
 
-            // we are at the correct position
-            if largest == ni {
-                break;
+#[inline]
+fn is_less<T, C: Compare<T>>(nodes: &[T], a: NodeIdx, b: NodeIdx, cmp: &C) -> bool {
+    cmp.compare(&nodes[a], &nodes[b]) == Ordering::Less
+}
+
+/// Move the node at `ni` up until its parent is not larger
+pub(crate) fn sift_up<T, C: Compare<T>>(nodes: &mut [T], mut ni: NodeIdx, arity: usize, cmp: &C) {
+    // while we have a parent and we are smaller than him
+    while ni != 0 {
+        let parent_id = (ni - 1) / arity;
+        if !is_less(nodes, ni, parent_id, cmp) {
+            break;
+        }
+        nodes.swap(ni, parent_id);
+        ni = parent_id;
+    }
+}
+
+/// Move the node at `ni` down until none of its children is smaller
+pub(crate) fn sift_down<T, C: Compare<T>>(nodes: &mut [T], mut ni: NodeIdx, arity: usize, cmp: &C) {
+    loop {
+        // for a min-heap we swap with the smallest child
+        let first_child = ni * arity + 1;
+        let last_child = (first_child + arity).min(nodes.len());
+        let mut smallest = ni;
+        for child in first_child..last_child {
+            if is_less(nodes, child, smallest, cmp) {
+                smallest = child;
             }
-            self.nodes.swap(ni, largest);
-            ni = largest;
         }
+
+        // we are at the correct position
+        if smallest == ni {
+            break;
+        }
+        nodes.swap(ni, smallest);
+        ni = smallest;
     }
+}
 
-    pub fn iter(&'_ self) -> std::slice::Iter<'_, T> {
-        self.nodes.iter()
+/// Lay out unordered nodes as a heap in O(n)
+pub(crate) fn heapify<T, C: Compare<T>>(nodes: &mut [T], arity: usize, cmp: &C) {
+    // only the nodes having children need to be sifted
+    for ni in (0..nodes.len().div_ceil(arity)).rev() {
+        sift_down(nodes, ni, arity, cmp);
+    }
+}
+
+/// Guard returned by [`BinaryHeap::peek_mut`], the root is sifted down on drop
+pub struct PeekMut<'a, T, C: Compare<T> = MinOrder> {
+    heap: &'a mut BinaryHeap<T, C>,
+}
+
+impl<T, C: Compare<T>> PeekMut<'_, T, C> {
+    /// Remove the peeked element from the heap
+    pub fn pop(this: Self) -> T {
+        // the heap is already valid once the root is popped, sifting it again on drop is a no-op
+        this.heap
+            .pop()
+            .expect("PeekMut is only created for non-empty heaps")
+    }
+}
+
+impl<T, C: Compare<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.nodes[0]
+    }
+}
+
+impl<T, C: Compare<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.heap.nodes[0]
+    }
+}
+
+impl<T, C: Compare<T>> Drop for PeekMut<'_, T, C> {
+    fn drop(&mut self) {
+        // the root may have grown larger than its children
+        self.heap.heapify_down(0);
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for BinaryHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec_with(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for BinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::Rng;
 
     #[test]
     fn test_empty_heap() {
@@ -179,4 +376,130 @@ mod tests {
         heap.push(0);
         assert_eq!(heap.pop(), Some(0));
     }
+
+    #[test]
+    fn test_peek() {
+        let mut heap = BinaryHeap::new();
+        assert_eq!(heap.peek(), None);
+        assert!(heap.peek_mut().is_none());
+        heap.extend([5, 3, 8, 1]);
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.len(), 4);
+    }
+
+    #[test]
+    fn test_peek_mut() {
+        let mut heap: BinaryHeap<i32> = [5, 3, 8, 1].into_iter().collect();
+        if let Some(mut top) = heap.peek_mut() {
+            *top = 10;
+        }
+        // the old root sank down after the guard was dropped
+        assert_eq!(heap.peek(), Some(&3));
+
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![5, 8, 10]);
+    }
+
+    #[test]
+    fn test_into_sorted_vec() {
+        let heap = BinaryHeap::from_vec(vec![3, 1, 4, 1, 5, 9, 2, 6, 5]);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 2, 3, 4, 5, 5, 6, 9]);
+    }
+
+    #[test]
+    fn test_random_against_sorted() {
+        let mut rng = rand::thread_rng();
+        let mut heap = BinaryHeap::new();
+        let mut oracle = Vec::new();
+        for _ in 0..5000 {
+            let val = rng.gen_range(-1000..1000);
+            heap.push(val);
+            oracle.push(val);
+        }
+        oracle.sort();
+
+        for expected in oracle {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_random_interleaved() {
+        let mut rng = rand::thread_rng();
+        let mut heap = BinaryHeap::new();
+        let mut oracle = std::collections::BinaryHeap::new();
+        for _ in 0..10000 {
+            if rng.gen_bool(0.6) {
+                let val = rng.gen_range(0..500);
+                heap.push(val);
+                oracle.push(std::cmp::Reverse(val));
+            } else {
+                assert_eq!(heap.pop(), oracle.pop().map(|r| r.0));
+            }
+            assert_eq!(heap.peek(), oracle.peek().map(|r| &r.0));
+        }
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = BinaryHeap::new_max();
+        heap.extend([4, 2, 9, 11, 7]);
+        assert_eq!(heap.peek(), Some(&11));
+        assert_eq!(heap.pop(), Some(11));
+        assert_eq!(heap.pop(), Some(9));
+        if let Some(mut top) = heap.peek_mut() {
+            *top = 0;
+        }
+        assert_eq!(heap.into_sorted_vec(), vec![4, 2, 0]);
+
+        let heap: BinaryHeap<_, MaxOrder> = [3, 1, 4, 1, 5].into_iter().collect();
+        assert_eq!(heap.into_sorted_vec(), vec![5, 4, 3, 1, 1]);
+    }
+
+    #[test]
+    fn test_min_heap_constructors() {
+        let mut heap = BinaryHeap::new_min();
+        heap.extend([4, 2, 9]);
+        assert_eq!(heap.pop(), Some(2));
+        let heap: BinaryHeap<i32> = BinaryHeap::default();
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_with_key() {
+        let mut heap = BinaryHeap::with_key(|s: &&str| s.len());
+        heap.extend(["ccc", "a", "dddd", "bb"]);
+        assert_eq!(heap.pop(), Some("a"));
+        assert_eq!(heap.pop(), Some("bb"));
+        assert_eq!(heap.pop(), Some("ccc"));
+        assert_eq!(heap.pop(), Some("dddd"));
+    }
+
+    #[test]
+    fn test_with_comparator() {
+        // values without a total order can be used with a custom comparator
+        let mut heap = BinaryHeap::with_comparator(|a: &f64, b: &f64| b.total_cmp(a));
+        heap.extend([1.5, -2.0, 8.25, 3.0]);
+        assert_eq!(heap.pop(), Some(8.25));
+        assert_eq!(heap.pop(), Some(3.0));
+
+        let offset = 10;
+        let heap = BinaryHeap::from_vec_with(vec![12, 5, 9, 14], |a: &i32, b: &i32| {
+            (a - offset).abs().cmp(&(b - offset).abs())
+        });
+        assert_eq!(heap.into_sorted_vec(), vec![9, 12, 14, 5]);
+    }
+
+    #[test]
+    fn test_random_max_against_sorted() {
+        let mut rng = rand::thread_rng();
+        let mut oracle = (0..3000)
+            .map(|_| rng.gen_range(-1000..1000))
+            .collect::<Vec<i32>>();
+        let heap = BinaryHeap::from_vec_with(oracle.clone(), MaxOrder);
+        oracle.sort_by(|a, b| b.cmp(a));
+        assert_eq!(heap.into_sorted_vec(), oracle);
+    }
 }
@@ -0,0 +1,188 @@
+use std::fmt::{self, Debug};
+
+use crate::data_structure::binary_heap::{heapify, sift_down, sift_up, Compare, HeapVal, MinOrder};
+
+/// d-ary heap implementation, a min-heap by default
+///
+/// Every node has `D` children instead of 2. The heap is shallower so pushing is cheaper,
+/// at the cost of comparing more children when popping.
+/// This pays off for workloads with many more pushes than pops.
+pub struct DaryHeap<T, const D: usize, C = MinOrder> {
+    pub nodes: Vec<T>,
+    cmp: C,
+}
+
+impl<T: Debug, const D: usize, C: Compare<T>> Debug for DaryHeap<T, D, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DaryHeap")
+            .field("nodes", &self.nodes)
+            .finish()
+    }
+}
+
+impl<T, const D: usize, C: Compare<T> + Default> Default for DaryHeap<T, D, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T: HeapVal, const D: usize> DaryHeap<T, D> {
+    pub const fn new() -> Self {
+        const { assert!(D >= 2, "a heap needs at least 2 children per node") };
+        Self {
+            nodes: Vec::new(),
+            cmp: MinOrder,
+        }
+    }
+
+    pub fn from_vec(nodes: Vec<T>) -> Self {
+        Self::from_vec_with(nodes, MinOrder)
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> DaryHeap<T, D, C> {
+    pub fn with_comparator(cmp: C) -> Self {
+        const { assert!(D >= 2, "a heap needs at least 2 children per node") };
+        Self {
+            nodes: Vec::new(),
+            cmp,
+        }
+    }
+
+    pub fn from_vec_with(nodes: Vec<T>, cmp: C) -> Self {
+        let mut heap = Self::with_comparator(cmp);
+        heap.nodes = nodes;
+        heapify(&mut heap.nodes, D, &heap.cmp);
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn push(&mut self, val: T) {
+        let new_node_id = self.nodes.len();
+        self.nodes.push(val);
+        sift_up(&mut self.nodes, new_node_id, D, &self.cmp);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let last = self.nodes.len() - 1;
+        self.nodes.swap(0, last);
+        let popped = self.nodes.pop();
+        if !self.nodes.is_empty() {
+            sift_down(&mut self.nodes, 0, D, &self.cmp);
+        }
+        popped
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.nodes.first()
+    }
+
+    /// Consume the heap, returning its elements in the order they would be popped
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(val) = self.pop() {
+            sorted.push(val);
+        }
+        sorted
+    }
+
+    pub fn iter(&'_ self) -> std::slice::Iter<'_, T> {
+        self.nodes.iter()
+    }
+}
+
+impl<T, const D: usize, C: Compare<T> + Default> FromIterator<T> for DaryHeap<T, D, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec_with(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> Extend<T> for DaryHeap<T, D, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::DaryHeap;
+    use crate::data_structure::MaxOrder;
+
+    fn check_random<const D: usize>() {
+        let mut rng = rand::thread_rng();
+        let mut heap = DaryHeap::<i32, D>::new();
+        let mut oracle = std::collections::BinaryHeap::new();
+        for _ in 0..5000 {
+            if rng.gen_bool(0.6) {
+                let val = rng.gen_range(0..1000);
+                heap.push(val);
+                oracle.push(std::cmp::Reverse(val));
+            } else {
+                assert_eq!(heap.pop(), oracle.pop().map(|r| r.0));
+            }
+            assert_eq!(heap.peek(), oracle.peek().map(|r| &r.0));
+        }
+
+        let mut vals = (0..2000)
+            .map(|_| rng.gen_range(-500..500))
+            .collect::<Vec<_>>();
+        let heap = DaryHeap::<_, D>::from_vec(vals.clone());
+        vals.sort();
+        assert_eq!(heap.into_sorted_vec(), vals);
+    }
+
+    #[test]
+    fn test_empty_heap() {
+        let mut heap: DaryHeap<usize, 4> = DaryHeap::new();
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_binary() {
+        check_random::<2>();
+    }
+
+    #[test]
+    fn test_quaternary() {
+        check_random::<4>();
+    }
+
+    #[test]
+    fn test_octonary() {
+        check_random::<8>();
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut heap = DaryHeap::<_, 4>::from_vec(vec![3, 1, 4, 1, 5, 9, 2, 6, 5]);
+        assert_eq!(heap.len(), 9);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        heap.push(0);
+        assert_eq!(heap.pop(), Some(0));
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let heap: DaryHeap<_, 8, MaxOrder> = [3, 1, 4, 1, 5, 9, 2, 6].into_iter().collect();
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(heap.into_sorted_vec(), vec![9, 6, 5, 4, 3, 2, 1, 1]);
+    }
+}
@@ -1,15 +1,58 @@
+/// How the roots of two trees are chosen when they are merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnionPolicy {
+    /// The tree with the higher rank becomes the parent
+    #[default]
+    Rank,
+    /// The tree with the more elements becomes the parent
+    Size,
+}
+
 pub struct DisjointSet {
     parent: Vec<usize>,
     // a rank is an upper bound on the height of a tree
     // rank is used to keep the tree flat during union
     rank: Vec<usize>,
+    // number of elements of the set, only valid for roots
+    size: Vec<usize>,
+    count: usize,
+    policy: UnionPolicy,
 }
 
 impl DisjointSet {
     pub fn new(size: usize) -> Self {
+        Self::with_policy(size, UnionPolicy::default())
+    }
+
+    pub fn with_policy(size: usize, policy: UnionPolicy) -> Self {
         let parent = (0..size).collect();
         let rank = vec![0; size];
-        Self { parent, rank }
+        Self {
+            parent,
+            rank,
+            size: vec![1; size],
+            count: size,
+            policy,
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Add a new element in its own set, returns its index
+    pub fn add_element(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.rank.push(0);
+        self.size.push(1);
+        self.count += 1;
+        x
     }
 
     pub fn find(&mut self, mut x: usize) -> usize {
@@ -26,23 +69,69 @@ impl DisjointSet {
         x
     }
 
-    // union by rank
-    pub fn union(&mut self, x: usize, y: usize) {
+    /// Merge the sets of `x` and `y`, returns false if they were already in the same set
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
         let root_x = self.find(x);
         let root_y = self.find(y);
+        if root_x == root_y {
+            return false;
+        }
 
-        if root_x != root_y {
-            // the larger tree becomes the parent
-            if self.rank[root_x] > self.rank[root_y] {
-                self.parent[root_y] = root_x;
-            } else if self.rank[root_x] < self.rank[root_y] {
-                self.parent[root_x] = root_y;
-            } else {
-                // if ranks are the same, either one can be the parent
-                self.parent[root_y] = root_x;
-                self.rank[root_x] += 1;
+        let (parent, child) = match self.policy {
+            UnionPolicy::Rank => {
+                // the larger tree becomes the parent
+                if self.rank[root_x] > self.rank[root_y] {
+                    (root_x, root_y)
+                } else if self.rank[root_x] < self.rank[root_y] {
+                    (root_y, root_x)
+                } else {
+                    // if ranks are the same, either one can be the parent
+                    self.rank[root_x] += 1;
+                    (root_x, root_y)
+                }
             }
+            UnionPolicy::Size => {
+                if self.size[root_x] >= self.size[root_y] {
+                    (root_x, root_y)
+                } else {
+                    (root_y, root_x)
+                }
+            }
+        };
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        self.count -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of elements in the set of `x`
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Number of disjoint sets
+    pub fn count_sets(&self) -> usize {
+        self.count
+    }
+
+    /// Elements grouped by set, the sets are ordered by their smallest element
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut group_of_root = vec![None; self.parent.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::with_capacity(self.count);
+        for x in 0..self.parent.len() {
+            let root = self.find(x);
+            let group = *group_of_root[root].get_or_insert_with(|| {
+                sets.push(Vec::with_capacity(self.size[root]));
+                sets.len() - 1
+            });
+            sets[group].push(x);
         }
+        sets
     }
 }
 
@@ -150,4 +239,52 @@ mod tests {
             assert_eq!(ds.find(i), root);
         }
     }
+
+    #[test]
+    fn test_union_result_and_count() {
+        let mut ds = DisjointSet::new(5);
+        assert_eq!(ds.count_sets(), 5);
+        assert!(ds.union(0, 1));
+        assert!(ds.union(3, 1));
+        assert!(!ds.union(0, 3));
+        assert_eq!(ds.count_sets(), 3);
+        assert!(ds.same_set(0, 3));
+        assert!(!ds.same_set(0, 4));
+    }
+
+    #[test]
+    fn test_sizes_and_sets() {
+        for policy in [UnionPolicy::Rank, UnionPolicy::Size] {
+            let mut ds = DisjointSet::with_policy(7, policy);
+            ds.union(5, 0);
+            ds.union(2, 4);
+            ds.union(4, 6);
+            assert_eq!(ds.size_of(0), 2);
+            assert_eq!(ds.size_of(6), 3);
+            assert_eq!(ds.size_of(3), 1);
+            assert_eq!(ds.sets(), vec![vec![0, 5], vec![1], vec![2, 4, 6], vec![3]]);
+
+            let x = ds.add_element();
+            assert_eq!(x, 7);
+            assert_eq!(ds.len(), 8);
+            assert_eq!(ds.count_sets(), 5);
+            ds.union(x, 1);
+            ds.union(x, 0);
+            assert_eq!(ds.size_of(5), 4);
+            assert_eq!(ds.sets(), vec![vec![0, 1, 5, 7], vec![2, 4, 6], vec![3]]);
+        }
+    }
+
+    #[test]
+    fn test_union_by_size() {
+        let mut ds = DisjointSet::with_policy(5, UnionPolicy::Size);
+        ds.union(0, 1);
+        ds.union(0, 2);
+        // the smaller set is attached under the root of the larger one
+        ds.union(3, 0);
+        let root = ds.find(0);
+        assert_eq!(ds.find(3), root);
+        assert_ne!(root, 3);
+        assert_eq!(ds.size_of(3), 4);
+    }
 }
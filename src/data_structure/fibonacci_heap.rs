@@ -0,0 +1,455 @@
+use std::{
+    cell::{Ref, RefCell},
+    fmt::{self, Debug},
+    rc::{Rc, Weak},
+};
+
+use crate::data_structure::{DecreaseKey, PriorityQueue};
+
+type Link<T> = Rc<RefCell<Node<T>>>;
+
+struct Node<T> {
+    val: T,
+    children: Vec<Link<T>>,
+    parent: Weak<RefCell<Node<T>>>,
+    // position in the children of the parent, so that cutting the node is O(1)
+    index: usize,
+    // set once the node lost a child since it became a child itself
+    marked: bool,
+}
+
+/// Reference to an element pushed in a [`FibonacciHeap`]
+///
+/// The handle becomes invalid once the element is popped or deleted.
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Handle<T> {
+    pub fn is_valid(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+}
+
+/// Fibonacci min-heap implementation
+///
+/// A list of heap-ordered trees, only consolidated when popping.
+/// push, meld and decrease_key are O(1) amortized, pop is O(log n) amortized.
+pub struct FibonacciHeap<T: Ord> {
+    roots: Vec<Link<T>>,
+    min: Option<Link<T>>,
+    len: usize,
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Debug> Debug for FibonacciHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FibonacciHeap")
+            .field("min", &self.peek())
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub const fn new() -> Self {
+        Self {
+            roots: Vec::new(),
+            min: None,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        self.min
+            .as_ref()
+            .map(|min| Ref::map(min.borrow(), |node| &node.val))
+    }
+
+    pub fn push(&mut self, val: T) -> Handle<T> {
+        let node = Rc::new(RefCell::new(Node {
+            val,
+            children: Vec::new(),
+            parent: Weak::new(),
+            index: 0,
+            marked: false,
+        }));
+        let handle = Handle(Rc::downgrade(&node));
+        self.add_root(node);
+        self.len += 1;
+        handle
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let min = self.min.take()?;
+        let pos = self
+            .roots
+            .iter()
+            .position(|root| Rc::ptr_eq(root, &min))
+            .expect("the min is a root");
+        self.roots.swap_remove(pos);
+
+        let children = std::mem::take(&mut min.borrow_mut().children);
+        for child in children {
+            let mut node = child.borrow_mut();
+            node.parent = Weak::new();
+            node.marked = false;
+            drop(node);
+            self.roots.push(child);
+        }
+        self.consolidate();
+        self.len -= 1;
+
+        match Rc::try_unwrap(min) {
+            Ok(node) => Some(node.into_inner().val),
+            Err(_) => unreachable!("detached nodes are only referenced by weak handles"),
+        }
+    }
+
+    /// Move all the elements of `other` into this heap
+    ///
+    /// The handles of `other` stay valid and now refer to this heap.
+    pub fn meld(&mut self, mut other: Self) {
+        if let Some(min) = other.min.take() {
+            self.update_min(&min);
+        }
+        self.roots.append(&mut other.roots);
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    /// Lower the value of the element behind `handle`
+    ///
+    /// Returns false if the handle is no longer valid or if `new` is not smaller than the current value.
+    /// The handle must come from this heap.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, new: T) -> bool {
+        let Some(node) = handle.0.upgrade() else {
+            return false;
+        };
+        if new >= node.borrow().val {
+            return false;
+        }
+        node.borrow_mut().val = new;
+
+        let parent = node.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            if node.borrow().val < parent.borrow().val {
+                self.cut(&node, &parent);
+                self.cascading_cut(parent);
+            }
+        }
+        self.update_min(&node);
+        true
+    }
+
+    /// Remove the element behind `handle` from the heap
+    ///
+    /// Returns `None` if the handle is no longer valid. The handle must come from this heap.
+    pub fn delete(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = handle.0.upgrade()?;
+        let parent = node.borrow().parent.upgrade();
+        if let Some(parent) = parent {
+            self.cut(&node, &parent);
+            self.cascading_cut(parent);
+        }
+        // act as if the node had the smallest possible value
+        self.min = Some(node);
+        self.pop()
+    }
+
+    fn add_root(&mut self, node: Link<T>) {
+        self.update_min(&node);
+        self.roots.push(node);
+    }
+
+    fn update_min(&mut self, node: &Link<T>) {
+        let is_smaller = match &self.min {
+            Some(min) => node.borrow().val < min.borrow().val,
+            None => true,
+        };
+        if is_smaller {
+            self.min = Some(node.clone());
+        }
+    }
+
+    /// Move `node` from the children of `parent` to the roots
+    fn cut(&mut self, node: &Link<T>, parent: &Link<T>) {
+        // the last child takes the place of the node
+        let index = node.borrow().index;
+        let mut parent = parent.borrow_mut();
+        parent.children.swap_remove(index);
+        if let Some(moved) = parent.children.get(index) {
+            moved.borrow_mut().index = index;
+        }
+        drop(parent);
+        let mut n = node.borrow_mut();
+        n.parent = Weak::new();
+        n.marked = false;
+        drop(n);
+        self.roots.push(node.clone());
+    }
+
+    /// Cut the ancestors which already lost a child, mark the first one which did not
+    fn cascading_cut(&mut self, mut node: Link<T>) {
+        loop {
+            let parent = node.borrow().parent.upgrade();
+            let Some(parent) = parent else {
+                return;
+            };
+            if !node.borrow().marked {
+                node.borrow_mut().marked = true;
+                return;
+            }
+            self.cut(&node, &parent);
+            node = parent;
+        }
+    }
+
+    /// Link the roots of equal degree until all the degrees are distinct
+    fn consolidate(&mut self) {
+        let mut by_degree: Vec<Option<Link<T>>> = Vec::new();
+        for mut root in std::mem::take(&mut self.roots) {
+            loop {
+                let degree = root.borrow().children.len();
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+                let Some(other) = by_degree[degree].take() else {
+                    by_degree[degree] = Some(root);
+                    break;
+                };
+                let (parent, child) = if other.borrow().val < root.borrow().val {
+                    (other, root)
+                } else {
+                    (root, other)
+                };
+                {
+                    let mut child_node = child.borrow_mut();
+                    child_node.parent = Rc::downgrade(&parent);
+                    child_node.index = parent.borrow().children.len();
+                }
+                parent.borrow_mut().children.push(child);
+                root = parent;
+            }
+        }
+
+        self.min = None;
+        for root in by_degree.into_iter().flatten() {
+            self.add_root(root);
+        }
+    }
+}
+
+impl<T: Ord> Drop for FibonacciHeap<T> {
+    fn drop(&mut self) {
+        // unlink the nodes iteratively to avoid a recursive drop overflowing the stack
+        self.min = None;
+        let mut stack = std::mem::take(&mut self.roots);
+        while let Some(node) = stack.pop() {
+            stack.append(&mut node.borrow_mut().children);
+        }
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for FibonacciHeap<T> {
+    type Handle = Handle<T>;
+
+    fn push(&mut self, val: T) -> Handle<T> {
+        FibonacciHeap::push(self, val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        FibonacciHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        FibonacciHeap::len(self)
+    }
+}
+
+impl<T: Ord> DecreaseKey<T> for FibonacciHeap<T> {
+    fn decrease_key(&mut self, handle: &Handle<T>, new: T) -> bool {
+        FibonacciHeap::decrease_key(self, handle, new)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for FibonacciHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for FibonacciHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::FibonacciHeap;
+
+    #[test]
+    fn test_empty_heap() {
+        let mut heap: FibonacciHeap<usize> = FibonacciHeap::new();
+        assert_eq!(heap.pop(), None);
+        assert!(heap.peek().is_none());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap = FibonacciHeap::new();
+        heap.push(4);
+        heap.push(2);
+        heap.push(9);
+        heap.push(11);
+        assert_eq!(heap.len(), 4);
+        assert_eq!(*heap.peek().unwrap(), 2);
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), Some(9));
+        heap.push(1);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(11));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut a: FibonacciHeap<_> = [5, 1, 8].into_iter().collect();
+        let mut b = FibonacciHeap::new();
+        let h = b.push(7);
+        b.extend([0, 3]);
+
+        a.meld(b);
+        assert_eq!(a.len(), 6);
+        assert_eq!(*a.peek().unwrap(), 0);
+        // handles survive the meld
+        assert!(a.decrease_key(&h, -1));
+        assert_eq!(a.pop(), Some(-1));
+        assert_eq!(a.pop(), Some(0));
+        assert_eq!(a.pop(), Some(1));
+        assert_eq!(a.pop(), Some(3));
+        assert_eq!(a.pop(), Some(5));
+        assert_eq!(a.pop(), Some(8));
+        assert_eq!(a.pop(), None);
+    }
+
+    #[test]
+    fn test_decrease_key_and_delete() {
+        let mut heap = FibonacciHeap::new();
+        let handles = (0..10).map(|i| heap.push(i * 10)).collect::<Vec<_>>();
+        // consolidate the roots into trees
+        assert_eq!(heap.pop(), Some(0));
+        assert!(!handles[0].is_valid());
+        assert!(!heap.decrease_key(&handles[0], -5));
+
+        assert!(heap.decrease_key(&handles[7], 5));
+        assert!(!heap.decrease_key(&handles[3], 35));
+        assert_eq!(heap.delete(&handles[1]), Some(10));
+        assert_eq!(heap.delete(&handles[1]), None);
+        assert_eq!(heap.delete(&handles[7]), Some(5));
+
+        assert_eq!(heap.len(), 7);
+        let mut popped = Vec::new();
+        while let Some(val) = heap.pop() {
+            popped.push(val);
+        }
+        assert_eq!(popped, vec![20, 30, 40, 50, 60, 80, 90]);
+    }
+
+    #[test]
+    fn test_random_against_sorted() {
+        let mut rng = rand::thread_rng();
+        let mut heap = FibonacciHeap::new();
+        let mut oracle = Vec::new();
+        let mut handles = Vec::new();
+        for _ in 0..3000 {
+            let val = rng.gen_range(0..100_000);
+            handles.push((heap.push(val), val));
+        }
+        // build deep trees so that decrease_key triggers cascading cuts
+        heap.push(-1);
+        assert_eq!(heap.pop(), Some(-1));
+        for (handle, val) in handles.iter_mut().take(1000) {
+            let new = rng.gen_range(0..=*val);
+            assert_eq!(heap.decrease_key(handle, new), new < *val);
+            *val = new;
+        }
+        for (i, (handle, val)) in handles.into_iter().enumerate() {
+            if i % 7 == 0 {
+                assert_eq!(heap.delete(&handle), Some(val));
+            } else {
+                oracle.push(val);
+            }
+        }
+        oracle.sort();
+
+        assert_eq!(heap.len(), oracle.len());
+        for expected in oracle {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_interleaved_cuts() {
+        // pops build deep trees, then decreases and deletes cut nodes out of them
+        let mut rng = rand::thread_rng();
+        let mut heap = FibonacciHeap::new();
+        let mut live = Vec::new();
+        for step in 0..20_000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let val = rng.gen_range(0..1_000_000);
+                    live.push((heap.push(val), val));
+                }
+                2 if !live.is_empty() => {
+                    let i = rng.gen_range(0..live.len());
+                    let new = live[i].1 - rng.gen_range(0..=live[i].1.min(1000));
+                    assert_eq!(heap.decrease_key(&live[i].0, new), new < live[i].1);
+                    live[i].1 = new;
+                }
+                _ if !live.is_empty() => {
+                    if step % 2 == 0 {
+                        let i = rng.gen_range(0..live.len());
+                        let (handle, val) = live.swap_remove(i);
+                        assert_eq!(heap.delete(&handle), Some(val));
+                    } else {
+                        let min = live.iter().map(|(_, val)| *val).min();
+                        let popped = heap.pop();
+                        assert_eq!(popped, min);
+                        let i = live.iter().position(|(h, _)| !h.is_valid()).unwrap();
+                        live.swap_remove(i);
+                    }
+                }
+                _ => {}
+            }
+            assert_eq!(heap.len(), live.len());
+        }
+    }
+}
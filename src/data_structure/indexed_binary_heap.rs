@@ -0,0 +1,214 @@
+use crate::data_structure::binary_heap::{HeapVal, NodeIdx};
+
+/// Binary min-heap of keys ordered by a priority, supporting decrease_key
+///
+/// Keys are small integers such as node ids, their position in the heap is tracked
+/// so that the priority of a queued key can be updated in O(log n).
+#[derive(Debug)]
+pub struct IndexedBinaryHeap<P: HeapVal> {
+    // (key, priority) pairs laid out as a binary heap
+    nodes: Vec<(usize, P)>,
+    // position of every key in `nodes`, if queued
+    positions: Vec<Option<NodeIdx>>,
+}
+
+impl<P: HeapVal> Default for IndexedBinaryHeap<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: HeapVal> IndexedBinaryHeap<P> {
+    pub const fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        self.position(key).is_some()
+    }
+
+    pub fn priority_of(&self, key: usize) -> Option<&P> {
+        self.position(key).map(|ni| &self.nodes[ni].1)
+    }
+
+    /// Queue `key` with `priority`, returns false if `key` is already queued
+    pub fn push(&mut self, key: usize, priority: P) -> bool {
+        if self.contains(key) {
+            return false;
+        }
+        if key >= self.positions.len() {
+            self.positions.resize(key + 1, None);
+        }
+        let ni = self.nodes.len();
+        self.nodes.push((key, priority));
+        self.positions[key] = Some(ni);
+        self.heapify_up(ni);
+        true
+    }
+
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        self.nodes.first().map(|(key, priority)| (*key, priority))
+    }
+
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let last = self.nodes.len() - 1;
+        self.swap(0, last);
+        let (key, priority) = self.nodes.pop()?;
+        self.positions[key] = None;
+        if !self.nodes.is_empty() {
+            self.heapify_down(0);
+        }
+        Some((key, priority))
+    }
+
+    /// Lower the priority of a queued key
+    ///
+    /// Returns false if `key` is not queued or if `priority` is not smaller than its current one.
+    pub fn decrease_key(&mut self, key: usize, priority: P) -> bool {
+        let Some(ni) = self.position(key) else {
+            return false;
+        };
+        if priority >= self.nodes[ni].1 {
+            return false;
+        }
+        self.nodes[ni].1 = priority;
+        // a smaller priority can only move the key up
+        self.heapify_up(ni);
+        true
+    }
+
+    fn position(&self, key: usize) -> Option<NodeIdx> {
+        self.positions.get(key).copied().flatten()
+    }
+
+    /// Swap two nodes while keeping track of their positions
+    fn swap(&mut self, a: NodeIdx, b: NodeIdx) {
+        self.nodes.swap(a, b);
+        self.positions[self.nodes[a].0] = Some(a);
+        self.positions[self.nodes[b].0] = Some(b);
+    }
+
+    fn heapify_up(&mut self, mut ni: NodeIdx) {
+        while ni != 0 {
+            let parent = (ni - 1) / 2;
+            if self.nodes[parent].1 <= self.nodes[ni].1 {
+                break;
+            }
+            self.swap(ni, parent);
+            ni = parent;
+        }
+    }
+
+    fn heapify_down(&mut self, mut ni: NodeIdx) {
+        loop {
+            let mut smallest = ni;
+            for child in [ni * 2 + 1, ni * 2 + 2] {
+                if child < self.nodes.len() && self.nodes[child].1 < self.nodes[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == ni {
+                break;
+            }
+            self.swap(ni, smallest);
+            ni = smallest;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::IndexedBinaryHeap;
+
+    #[test]
+    fn test_empty_heap() {
+        let mut heap: IndexedBinaryHeap<usize> = IndexedBinaryHeap::new();
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.peek(), None);
+        assert!(!heap.contains(0));
+        assert!(!heap.decrease_key(0, 1));
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap = IndexedBinaryHeap::new();
+        assert!(heap.push(3, 40));
+        assert!(heap.push(0, 20));
+        assert!(heap.push(7, 90));
+        assert!(!heap.push(0, 1));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.priority_of(0), Some(&20));
+        assert_eq!(heap.peek(), Some((0, &20)));
+
+        assert_eq!(heap.pop(), Some((0, 20)));
+        assert!(!heap.contains(0));
+        assert_eq!(heap.pop(), Some((3, 40)));
+        // popped keys can be queued again
+        assert!(heap.push(0, 50));
+        assert_eq!(heap.pop(), Some((0, 50)));
+        assert_eq!(heap.pop(), Some((7, 90)));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = IndexedBinaryHeap::new();
+        for key in 0..10 {
+            heap.push(key, 100 + key);
+        }
+        assert!(heap.decrease_key(9, 5));
+        assert!(heap.decrease_key(4, 50));
+        assert!(!heap.decrease_key(4, 60));
+        assert!(!heap.decrease_key(42, 0));
+        assert_eq!(heap.priority_of(9), Some(&5));
+
+        assert_eq!(heap.pop(), Some((9, 5)));
+        assert_eq!(heap.pop(), Some((4, 50)));
+        assert_eq!(heap.pop(), Some((0, 100)));
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::thread_rng();
+        let n = 2000;
+        let mut heap = IndexedBinaryHeap::new();
+        let mut oracle = (0..n)
+            .map(|_| Some(rng.gen_range(0..100_000)))
+            .collect::<Vec<_>>();
+        for (key, p) in oracle.iter().enumerate() {
+            heap.push(key, p.unwrap());
+        }
+        for _ in 0..n {
+            let key = rng.gen_range(0..n);
+            let p = rng.gen_range(0..100_000);
+            if heap.decrease_key(key, p) {
+                oracle[key] = Some(p);
+            }
+        }
+
+        let mut prev = 0;
+        while let Some((key, p)) = heap.pop() {
+            assert!(p >= prev);
+            assert_eq!(oracle[key].take(), Some(p));
+            prev = p;
+        }
+        assert!(oracle.iter().all(Option::is_none));
+    }
+}
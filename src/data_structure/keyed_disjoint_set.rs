@@ -0,0 +1,181 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use crate::data_structure::{DisjointSet, UnionPolicy};
+
+/// Disjoint set over arbitrary hashable keys
+///
+/// Keys are interned the first time they are seen, and mapped to the dense indices of a [`DisjointSet`].
+pub struct KeyedDisjointSet<K> {
+    set: DisjointSet,
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> Default for KeyedDisjointSet<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedDisjointSet<K> {
+    pub fn new() -> Self {
+        Self::with_policy(UnionPolicy::default())
+    }
+
+    pub fn with_policy(policy: UnionPolicy) -> Self {
+        Self {
+            set: DisjointSet::with_policy(0, policy),
+            indices: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.indices.contains_key(key)
+    }
+
+    /// Add `key` in its own set if it was never seen, returns false if it was already there
+    pub fn insert(&mut self, key: K) -> bool {
+        if self.contains(&key) {
+            return false;
+        }
+        self.intern(&key);
+        true
+    }
+
+    /// Representative key of the set of `key`, `None` if `key` was never seen
+    pub fn find<Q>(&mut self, key: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let x = *self.indices.get(key)?;
+        let root = self.set.find(x);
+        Some(&self.keys[root])
+    }
+
+    /// Merge the sets of `x` and `y`, adding the keys never seen before
+    ///
+    /// Returns false if they were already in the same set.
+    pub fn union(&mut self, x: &K, y: &K) -> bool {
+        let x = self.intern(x);
+        let y = self.intern(y);
+        self.set.union(x, y)
+    }
+
+    pub fn same_set<Q>(&mut self, x: &Q, y: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        match (self.indices.get(x), self.indices.get(y)) {
+            (Some(&x), Some(&y)) => self.set.same_set(x, y),
+            _ => x == y,
+        }
+    }
+
+    /// Number of disjoint sets
+    pub fn count_sets(&self) -> usize {
+        self.set.count_sets()
+    }
+
+    /// Keys grouped by set, in the order they were first seen
+    pub fn groups(&mut self) -> Vec<Vec<K>> {
+        self.set
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|x| self.keys[x].clone()).collect())
+            .collect()
+    }
+
+    /// Index of `key` in the underlying disjoint set, added if never seen
+    fn intern(&mut self, key: &K) -> usize {
+        if let Some(&x) = self.indices.get(key) {
+            return x;
+        }
+        let x = self.set.add_element();
+        self.indices.insert(key.clone(), x);
+        self.keys.push(key.clone());
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KeyedDisjointSet;
+
+    #[test]
+    fn test_strings() {
+        let mut ds = KeyedDisjointSet::new();
+        assert!(ds.union(&String::from("paris"), &String::from("lyon")));
+        assert!(ds.union(&String::from("berlin"), &String::from("munich")));
+        assert!(ds.union(&String::from("lyon"), &String::from("marseille")));
+        assert!(!ds.union(&String::from("marseille"), &String::from("paris")));
+        assert!(ds.insert(String::from("rome")));
+        assert!(!ds.insert(String::from("rome")));
+
+        assert_eq!(ds.len(), 6);
+        assert_eq!(ds.count_sets(), 3);
+        assert!(ds.same_set("paris", "marseille"));
+        assert!(!ds.same_set("paris", "berlin"));
+        assert!(!ds.same_set("paris", "london"));
+        assert!(ds.same_set("london", "london"));
+        assert_eq!(ds.find("london"), None);
+        assert_eq!(ds.find("rome").map(String::as_str), Some("rome"));
+
+        let root = ds.find("lyon").cloned();
+        assert_eq!(ds.find("marseille").cloned(), root);
+
+        assert_eq!(
+            ds.groups(),
+            vec![
+                vec!["paris", "lyon", "marseille"],
+                vec!["berlin", "munich"],
+                vec!["rome"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coordinates() {
+        // group the adjacent cells of a grid
+        let grid = ["##.#", "#..#", "..##", "#..."];
+        let mut ds = KeyedDisjointSet::new();
+        for (r, row) in grid.iter().enumerate() {
+            for (c, cell) in row.bytes().enumerate() {
+                if cell != b'#' {
+                    continue;
+                }
+                ds.insert((r, c));
+                if r > 0 && grid[r - 1].as_bytes()[c] == b'#' {
+                    ds.union(&(r, c), &(r - 1, c));
+                }
+                if c > 0 && row.as_bytes()[c - 1] == b'#' {
+                    ds.union(&(r, c), &(r, c - 1));
+                }
+            }
+        }
+        assert_eq!(ds.count_sets(), 3);
+        assert_eq!(
+            ds.groups(),
+            vec![
+                vec![(0, 0), (0, 1), (1, 0)],
+                vec![(0, 3), (1, 3), (2, 2), (2, 3)],
+                vec![(3, 0)],
+            ]
+        );
+    }
+}
@@ -1,121 +1,531 @@
-use std::ptr::NonNull;
+use std::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
 
-pub struct Node {
-    pub val: i32,
-    pub next: NodePtr,
+pub struct Node<T> {
+    pub val: T,
+    pub prev: NodePtr<T>,
+    pub next: NodePtr<T>,
 }
 
-pub type NodePtr = Option<NonNull<Node>>;
+pub type NodePtr<T> = Option<NonNull<Node<T>>>;
+
+/// Doubly linked list
+///
+/// `push` and `pop` are kept as aliases of `push_back` and `pop_front`, the list being used as a queue.
+pub struct LinkedList<T> {
+    head: NodePtr<T>,
+    tail: NodePtr<T>,
+    len: usize,
+    // the list owns its nodes, for the drop check
+    _marker: PhantomData<Box<Node<T>>>,
+}
 
-#[derive(Default)]
-pub struct LinkedList {
-    pub head: NodePtr,
-    pub tail: NodePtr,
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl LinkedList {
-    pub fn new() -> Self {
-        LinkedList::default()
+impl<T> LinkedList<T> {
+    pub const fn new() -> Self {
+        LinkedList {
+            head: None,
+            tail: None,
+            len: 0,
+            _marker: PhantomData,
+        }
     }
 
-    pub fn push(&mut self, val: i32) {
-        unsafe {
-            let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node { val, next: None })));
-            if let Some(old) = self.tail {
-                (*old.as_ptr()).next = Some(new);
-            } else {
-                self.head = Some(new);
-            }
-            self.tail = Some(new);
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push_front(&mut self, val: T) {
+        unsafe { self.link_between(None, self.head, val) };
+    }
+
+    pub fn push_back(&mut self, val: T) {
+        unsafe { self.link_between(self.tail, None, val) };
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|head| unsafe { self.unlink(head) })
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|tail| unsafe { self.unlink(tail) })
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.push_back(val);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        unsafe { self.head.map(|n| &(*n.as_ptr()).val) }
+    }
+
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.head.map(|n| &mut (*n.as_ptr()).val) }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        unsafe { self.tail.map(|n| &(*n.as_ptr()).val) }
+    }
+
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        unsafe { self.tail.map(|n| &mut (*n.as_ptr()).val) }
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.front_mut()
+    }
+
+    /// Move all the elements of `other` to the back of the list in O(1), leaving `other` empty
+    pub fn append(&mut self, other: &mut Self) {
+        let Some(other_head) = other.head.take() else {
+            return;
+        };
+        match self.tail {
+            Some(tail) => unsafe {
+                (*tail.as_ptr()).next = Some(other_head);
+                (*other_head.as_ptr()).prev = Some(tail);
+            },
+            None => self.head = Some(other_head),
         }
+        self.tail = other.tail.take();
+        self.len += std::mem::take(&mut other.len);
     }
 
-    pub fn pop(&mut self) -> Option<i32> {
+    /// Split the list in two at `at`, returning the elements from `at` onwards
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(
+            at <= self.len,
+            "cannot split off at {at}, the length is {}",
+            self.len
+        );
+        if at == self.len {
+            return Self::new();
+        }
+        if at == 0 {
+            return std::mem::take(self);
+        }
+
+        // walk from the closest end to the last node that is kept
+        let last_kept = if at <= self.len / 2 {
+            let mut node = self.head;
+            for _ in 0..at - 1 {
+                node = node.and_then(|n| unsafe { (*n.as_ptr()).next });
+            }
+            node
+        } else {
+            let mut node = self.tail;
+            for _ in 0..self.len - at {
+                node = node.and_then(|n| unsafe { (*n.as_ptr()).prev });
+            }
+            node
+        }
+        .expect("at is within the list");
+
         unsafe {
-            if let Some(head) = self.head.take() {
-                let head = Box::from_raw(head.as_ptr());
-                self.head = head.next;
-                if self.head.is_none() {
-                    self.tail = None;
-                }
-                return Some(head.val);
+            let new_head = (*last_kept.as_ptr()).next.take();
+            if let Some(new_head) = new_head {
+                (*new_head.as_ptr()).prev = None;
             }
-            None
+            let other = LinkedList {
+                head: new_head,
+                tail: self.tail,
+                len: self.len - at,
+                _marker: PhantomData,
+            };
+            self.tail = Some(last_kept);
+            self.len = at;
+            other
         }
     }
 
-    pub fn peek(&self) -> Option<&i32> {
-        unsafe { self.head.map(|n| &(*n.as_ptr()).val) }
+    /// Reverse the order of the elements in O(n) without moving them
+    pub fn reverse(&mut self) {
+        let mut node = self.head;
+        while let Some(n) = node {
+            unsafe {
+                let n = &mut *n.as_ptr();
+                std::mem::swap(&mut n.prev, &mut n.next);
+                // the next node is now the previous one
+                node = n.prev;
+            }
+        }
+        std::mem::swap(&mut self.head, &mut self.tail);
     }
 
-    pub fn peek_mut(&mut self) -> Option<&mut i32> {
-        unsafe { self.head.map(|n| &mut (*n.as_ptr()).val) }
+    /// Cursor starting at the front of the list
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: self.head.map(|_| 0),
+            list: self,
+        }
     }
 
-    pub fn iter(&self) -> LinkedListIter<'_> {
-        LinkedListIter { next: &self.head }
+    pub fn iter(&self) -> LinkedListIter<'_, T> {
+        LinkedListIter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
+        }
     }
 
-    pub fn iter_mut(&mut self) -> LinkedListIterMut<'_> {
+    pub fn iter_mut(&mut self) -> LinkedListIterMut<'_, T> {
         LinkedListIterMut {
-            next: &mut self.head,
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            _marker: PhantomData,
         }
     }
+
+    /// Push `val` at the front of the list, returning its node for a later O(1) access
+    pub(crate) fn push_front_node(&mut self, val: T) -> NonNull<Node<T>> {
+        unsafe { self.link_between(None, self.head, val) }
+    }
+
+    /// Move a node of this list to the front
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list.
+    pub(crate) unsafe fn move_to_front(&mut self, node: NonNull<Node<T>>) {
+        if self.head == Some(node) {
+            return;
+        }
+        let n = &mut *node.as_ptr();
+        // the node is not the head so it has a previous node
+        if let Some(prev) = n.prev {
+            (*prev.as_ptr()).next = n.next;
+        }
+        match n.next {
+            Some(next) => (*next.as_ptr()).prev = n.prev,
+            None => self.tail = n.prev,
+        }
+        n.prev = None;
+        n.next = self.head;
+        if let Some(head) = self.head {
+            (*head.as_ptr()).prev = Some(node);
+        }
+        self.head = Some(node);
+    }
+
+    /// Insert a new node holding `val` between two adjacent nodes, `None` standing for the ends of the list
+    unsafe fn link_between(
+        &mut self,
+        prev: NodePtr<T>,
+        next: NodePtr<T>,
+        val: T,
+    ) -> NonNull<Node<T>> {
+        let new = NonNull::new_unchecked(Box::into_raw(Box::new(Node { val, prev, next })));
+        match prev {
+            Some(prev) => (*prev.as_ptr()).next = Some(new),
+            None => self.head = Some(new),
+        }
+        match next {
+            Some(next) => (*next.as_ptr()).prev = Some(new),
+            None => self.tail = Some(new),
+        }
+        self.len += 1;
+        new
+    }
+
+    /// Remove a node of this list and free it, returning its value
+    ///
+    /// # Safety
+    ///
+    /// `node` must be a node of this list.
+    pub(crate) unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        let node = Box::from_raw(node.as_ptr());
+        match node.prev {
+            Some(prev) => (*prev.as_ptr()).next = node.next,
+            None => self.head = node.next,
+        }
+        match node.next {
+            Some(next) => (*next.as_ptr()).prev = node.prev,
+            None => self.tail = node.prev,
+        }
+        self.len -= 1;
+        node.val
+    }
 }
 
-impl Drop for LinkedList {
+impl<T> Drop for LinkedList<T> {
     fn drop(&mut self) {
-        while self.pop().is_some() {}
+        while self.pop_front().is_some() {}
+    }
+}
+
+/// Cursor allowing to insert and remove elements anywhere in the list in O(1)
+///
+/// The cursor either points to an element, or to a "ghost" position between the back and the front of the list.
+/// Moving past either end of the list lands on the ghost, moving again wraps around.
+pub struct CursorMut<'a, T> {
+    list: &'a mut LinkedList<T>,
+    current: NodePtr<T>,
+    index: Option<usize>,
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Position of the current element, `None` on the ghost
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.current.map(|n| &mut (*n.as_ptr()).val) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            Some(n) => unsafe { (*n.as_ptr()).next },
+            None => self.list.head,
+        };
+        unsafe { next.map(|n| &mut (*n.as_ptr()).val) }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            Some(n) => unsafe { (*n.as_ptr()).prev },
+            None => self.list.tail,
+        };
+        unsafe { prev.map(|n| &mut (*n.as_ptr()).val) }
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            Some(n) => {
+                self.current = unsafe { (*n.as_ptr()).next };
+                self.index = self.current.and_then(|_| self.index.map(|i| i + 1));
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = self.current.map(|_| 0);
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            Some(n) => {
+                self.current = unsafe { (*n.as_ptr()).prev };
+                self.index = self.current.and_then(|_| self.index.map(|i| i - 1));
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.current.map(|_| self.list.len - 1);
+            }
+        }
+    }
+
+    /// Insert `val` after the current element, or at the front of the list on the ghost
+    pub fn insert_after(&mut self, val: T) {
+        unsafe {
+            match self.current {
+                Some(n) => self.list.link_between(Some(n), (*n.as_ptr()).next, val),
+                None => self.list.link_between(None, self.list.head, val),
+            };
+        }
+    }
+
+    /// Insert `val` before the current element, or at the back of the list on the ghost
+    pub fn insert_before(&mut self, val: T) {
+        unsafe {
+            match self.current {
+                Some(n) => {
+                    self.list.link_between((*n.as_ptr()).prev, Some(n), val);
+                    self.index = self.index.map(|i| i + 1);
+                }
+                None => {
+                    self.list.link_between(self.list.tail, None, val);
+                }
+            }
+        }
+    }
+
+    /// Remove the current element, the cursor moves to the next one
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        unsafe {
+            self.current = (*node.as_ptr()).next;
+            if self.current.is_none() {
+                self.index = None;
+            }
+            Some(self.list.unlink(node))
+        }
     }
 }
 
-pub struct LinkedListIntoIter(LinkedList);
-impl IntoIterator for LinkedList {
-    type Item = i32;
-    type IntoIter = LinkedListIntoIter;
+pub struct LinkedListIntoIter<T>(LinkedList<T>);
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = LinkedListIntoIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
         LinkedListIntoIter(self)
     }
 }
-impl Iterator for LinkedListIntoIter {
-    type Item = i32;
+impl<T> Iterator for LinkedListIntoIter<T> {
+    type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
-        self.0.pop()
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len, Some(self.0.len))
     }
 }
+impl<T> DoubleEndedIterator for LinkedListIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.pop_back()
+    }
+}
+impl<T> ExactSizeIterator for LinkedListIntoIter<T> {}
+impl<T> FusedIterator for LinkedListIntoIter<T> {}
+
+// The borrowing iterators walk the nodes from both ends through raw pointers,
+// `len` counts the nodes left between `head` and `tail` so that both ends never yield the same node.
 
-pub struct LinkedListIter<'a> {
-    next: &'a NodePtr,
+pub struct LinkedListIter<'a, T> {
+    head: NodePtr<T>,
+    tail: NodePtr<T>,
+    len: usize,
+    _marker: PhantomData<&'a Node<T>>,
 }
-impl<'a> Iterator for LinkedListIter<'a> {
-    type Item = &'a i32;
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = LinkedListIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T> Iterator for LinkedListIter<'a, T> {
+    type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.map(|node| unsafe {
-            self.next = &(*node.as_ptr()).next;
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|node| unsafe {
+            self.len -= 1;
+            self.head = (*node.as_ptr()).next;
+            &(*node.as_ptr()).val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+impl<T> DoubleEndedIterator for LinkedListIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            self.len -= 1;
+            self.tail = (*node.as_ptr()).prev;
             &(*node.as_ptr()).val
         })
     }
 }
+impl<T> ExactSizeIterator for LinkedListIter<'_, T> {}
+impl<T> FusedIterator for LinkedListIter<'_, T> {}
+impl<T> Clone for LinkedListIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
 
-pub struct LinkedListIterMut<'a> {
-    next: &'a NodePtr,
+pub struct LinkedListIterMut<'a, T> {
+    head: NodePtr<T>,
+    tail: NodePtr<T>,
+    len: usize,
+    // the iterator behaves as a unique borrow of the nodes
+    _marker: PhantomData<&'a mut Node<T>>,
 }
-impl<'a> Iterator for LinkedListIterMut<'a> {
-    type Item = &'a mut i32;
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = LinkedListIterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+impl<'a, T> Iterator for LinkedListIterMut<'a, T> {
+    type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.next.map(|node| unsafe {
-            self.next = &(*node.as_ptr()).next;
+        if self.len == 0 {
+            return None;
+        }
+        // every node is yielded at most once, so the returned references never alias
+        self.head.map(|node| unsafe {
+            self.len -= 1;
+            self.head = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).val
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+impl<T> DoubleEndedIterator for LinkedListIterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.tail.map(|node| unsafe {
+            self.len -= 1;
+            self.tail = (*node.as_ptr()).prev;
             &mut (*node.as_ptr()).val
         })
     }
 }
+impl<T> ExactSizeIterator for LinkedListIterMut<'_, T> {}
+impl<T> FusedIterator for LinkedListIterMut<'_, T> {}
+
+// The raw pointers opt out of Send and Sync, but the list owns its nodes like a Box would:
+// it can be sent or shared between threads whenever its elements can.
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+unsafe impl<T: Sync> Send for LinkedListIter<'_, T> {}
+unsafe impl<T: Sync> Sync for LinkedListIter<'_, T> {}
+unsafe impl<T: Send> Send for LinkedListIterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for LinkedListIterMut<'_, T> {}
+unsafe impl<T: Send> Send for CursorMut<'_, T> {}
+unsafe impl<T: Sync> Sync for CursorMut<'_, T> {}
 
 #[cfg(test)]
 mod test {
     use super::LinkedList;
+
+    fn collect<T: Clone>(list: &LinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
     #[test]
     fn basics() {
         let mut list = LinkedList::new();
@@ -141,6 +551,150 @@ mod test {
         assert_eq!(list.pop(), None);
     }
 
+    #[test]
+    fn both_ends() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.pop_back(), None);
+        list.push_front(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.front(), Some(&1));
+        assert_eq!(list.back(), Some(&3));
+
+        if let Some(x) = list.back_mut() {
+            *x *= 10;
+        }
+        assert_eq!(list.pop_back(), Some(30));
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.back(), None);
+
+        let mut list = LinkedList::new();
+        list.push_back(String::from("a"));
+        list.push_front(String::from("b"));
+        assert_eq!(collect(&list), vec!["b", "a"]);
+    }
+
+    #[test]
+    fn append() {
+        let mut a = LinkedList::new();
+        let mut b = LinkedList::new();
+        a.append(&mut b);
+        assert!(a.is_empty());
+
+        b.push(1);
+        b.push(2);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(b.pop_back(), None);
+
+        b.push(3);
+        b.push(4);
+        a.append(&mut b);
+        assert_eq!(a.len(), 4);
+        assert_eq!(collect(&a), vec![1, 2, 3, 4]);
+        assert_eq!(a.pop_back(), Some(4));
+        assert_eq!(a.back(), Some(&3));
+
+        // the emptied list is still usable
+        b.push(5);
+        assert_eq!(collect(&b), vec![5]);
+    }
+
+    #[test]
+    fn split_off() {
+        for at in 0..=6 {
+            let mut list = LinkedList::new();
+            for i in 0..6 {
+                list.push(i);
+            }
+            let other = list.split_off(at);
+            assert_eq!(list.len(), at);
+            assert_eq!(other.len(), 6 - at);
+            assert_eq!(collect(&list), (0..at).collect::<Vec<_>>());
+            assert_eq!(collect(&other), (at..6).collect::<Vec<_>>());
+            assert_eq!(list.back(), at.checked_sub(1).as_ref());
+            assert_eq!(other.front(), (at < 6).then_some(&at));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn split_off_out_of_bounds() {
+        let mut list = LinkedList::new();
+        list.push(1);
+        list.split_off(2);
+    }
+
+    #[test]
+    fn reverse() {
+        let mut list = LinkedList::new();
+        list.reverse();
+        for i in 0..5 {
+            list.push(i);
+        }
+        list.reverse();
+        assert_eq!(collect(&list), vec![4, 3, 2, 1, 0]);
+        assert_eq!(list.pop_back(), Some(0));
+        list.push_front(5);
+        assert_eq!(collect(&list), vec![5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn cursor() {
+        let mut list = LinkedList::new();
+        for i in 1..=5 {
+            list.push(i);
+        }
+
+        let mut cursor = list.cursor_mut();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+
+        // remove 3 then insert around 4
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 4));
+        assert_eq!(cursor.index(), Some(2));
+        cursor.insert_before(30);
+        cursor.insert_after(40);
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.peek_prev(), Some(&mut 30));
+        assert_eq!(cursor.peek_next(), Some(&mut 40));
+
+        // walk past the back to the ghost, then wrap around
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 5));
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 5));
+        cursor.insert_after(0);
+        cursor.insert_before(6);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(7));
+        assert_eq!(cursor.remove_current(), Some(6));
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 0));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 5));
+
+        assert_eq!(list.len(), 7);
+        assert_eq!(collect(&list), vec![0, 1, 2, 30, 4, 40, 5]);
+        assert_eq!(list.back(), Some(&5));
+    }
+
     #[test]
     fn into_iter() {
         let mut list = LinkedList::new();
@@ -169,6 +723,53 @@ mod test {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn double_ended() {
+        let mut list = LinkedList::new();
+        for i in 1..=5 {
+            list.push(i);
+        }
+
+        let mut iter = list.iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(
+            list.iter().rev().collect::<Vec<_>>(),
+            vec![&5, &4, &3, &2, &1]
+        );
+        for x in list.iter_mut().rev().take(2) {
+            *x *= 10;
+        }
+        assert_eq!((&list).into_iter().len(), 5);
+        assert_eq!(
+            list.into_iter().rev().collect::<Vec<_>>(),
+            vec![50, 40, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn send_sync() {
+        fn is_send<T: Send>() {}
+        fn is_sync<T: Sync>() {}
+
+        is_send::<LinkedList<i32>>();
+        is_sync::<LinkedList<i32>>();
+        is_send::<super::LinkedListIter<'_, i32>>();
+        is_sync::<super::LinkedListIter<'_, i32>>();
+        is_send::<super::LinkedListIterMut<'_, i32>>();
+        is_sync::<super::LinkedListIterMut<'_, i32>>();
+        is_send::<super::LinkedListIntoIter<i32>>();
+        is_send::<super::CursorMut<'_, i32>>();
+    }
+
     #[test]
     fn iter_mut() {
         let mut list = LinkedList::new();
@@ -219,5 +820,67 @@ mod test {
         }
         assert!(list.peek() == Some(&5000));
         list.push(7);
+
+        // exercise the links in both directions
+        let mut other = list.split_off(1);
+        other.push_front(8);
+        list.append(&mut other);
+        list.reverse();
+        let mut cursor = list.cursor_mut();
+        cursor.move_next();
+        cursor.insert_before(9);
+        assert_eq!(cursor.remove_current(), Some(600));
+        assert!(list.pop_back() == Some(5000));
+        assert!(list.pop_back() == Some(8));
+        assert!(list.pop_front() == Some(7));
+        assert!(list.pop_front() == Some(9));
+        assert!(list.is_empty());
+
+        // interleave mutable iteration, peeks and pushes, mirrored on a VecDeque
+        let mut oracle = std::collections::VecDeque::new();
+        for i in 0..4 {
+            list.push(i);
+            oracle.push_back(i);
+            let mut iter = list.iter_mut();
+            let front = iter.next();
+            let back = iter.next_back();
+            // both ends are live at the same time, and are the same node only once
+            match (front, back) {
+                (Some(front), Some(back)) => {
+                    *front += 10;
+                    *back += 20;
+                }
+                (Some(front), None) => *front += 10,
+                _ => unreachable!(),
+            }
+            for x in iter {
+                *x += 1;
+            }
+            let n = oracle.len();
+            for (j, x) in oracle.iter_mut().enumerate() {
+                *x += match j {
+                    0 => 10,
+                    j if j == n - 1 => 20,
+                    _ => 1,
+                };
+            }
+
+            if let Some(x) = list.peek_mut() {
+                *x *= 2;
+            }
+            oracle[0] *= 2;
+            list.push_front(100 + i);
+            oracle.push_front(100 + i);
+            if let Some(x) = list.back_mut() {
+                *x += 1;
+            }
+            oracle[n] += 1;
+            assert!(list.iter().eq(oracle.iter()));
+        }
+        assert_eq!(list.len(), 8);
+        let mut cursor = list.cursor_mut();
+        while cursor.remove_current().is_some() {}
+        assert!(list.is_empty());
+        assert_eq!(list.iter_mut().next(), None);
     }
 }
@@ -0,0 +1,306 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    fmt::{self, Debug},
+    hash::Hash,
+    ptr::NonNull,
+};
+
+use crate::data_structure::{linked_list::Node, LinkedList};
+
+/// Least recently used cache with a fixed capacity
+///
+/// The entries are kept in a linked list from the most to the least recently used,
+/// and indexed by a map from their key to their node so that every operation is O(1).
+pub struct LruCache<K, V> {
+    map: HashMap<K, NonNull<Node<(K, V)>>>,
+    list: LinkedList<(K, V)>,
+    capacity: usize,
+    on_evict: Option<Box<dyn FnMut(K, V)>>,
+}
+
+impl<K: Debug, V: Debug> Debug for LruCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruCache")
+            .field("entries", &self.list.iter().collect::<Vec<_>>())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "the capacity of a cache cannot be 0");
+        Self {
+            map: HashMap::with_capacity(capacity),
+            list: LinkedList::new(),
+            capacity,
+            on_evict: None,
+        }
+    }
+
+    /// Call `on_evict` with every entry evicted to make room, by `put` or `resize`
+    pub fn with_eviction_callback(mut self, on_evict: impl FnMut(K, V) + 'static) -> Self {
+        self.on_evict = Some(Box::new(on_evict));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Value of `key`, which becomes the most recently used entry
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = *self.map.get(key)?;
+        unsafe {
+            self.list.move_to_front(node);
+            Some(&(*node.as_ptr()).val.1)
+        }
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = *self.map.get(key)?;
+        unsafe {
+            self.list.move_to_front(node);
+            Some(&mut (*node.as_ptr()).val.1)
+        }
+    }
+
+    /// Value of `key`, without updating the order of use
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = *self.map.get(key)?;
+        unsafe { Some(&(*node.as_ptr()).val.1) }
+    }
+
+    /// Least recently used entry, the next one to be evicted
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.list.back().map(|(key, val)| (key, val))
+    }
+
+    /// Insert or update an entry, which becomes the most recently used one
+    ///
+    /// Returns the previous value of `key`. If the cache is full, the least recently used entry is evicted.
+    pub fn put(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(&node) = self.map.get(&key) {
+            unsafe {
+                self.list.move_to_front(node);
+                return Some(std::mem::replace(&mut (*node.as_ptr()).val.1, val));
+            }
+        }
+
+        if self.len() == self.capacity {
+            self.evict();
+        }
+        let node = self.list.push_front_node((key.clone(), val));
+        self.map.insert(key, node);
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let node = self.map.remove(key)?;
+        unsafe { Some(self.list.unlink(node).1) }
+    }
+
+    /// Remove the least recently used entry, the eviction callback is not called
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let (key, val) = self.list.pop_back()?;
+        self.map.remove(&key);
+        Some((key, val))
+    }
+
+    /// Change the capacity, evicting the least recently used entries that no longer fit
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn resize(&mut self, capacity: usize) {
+        assert!(capacity > 0, "the capacity of a cache cannot be 0");
+        while self.len() > capacity {
+            self.evict();
+        }
+        self.capacity = capacity;
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.list = LinkedList::new();
+    }
+
+    /// Entries from the most to the least recently used
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.list.iter().map(|(key, val)| (key, val))
+    }
+
+    fn evict(&mut self) {
+        if let Some((key, val)) = self.pop_lru() {
+            if let Some(on_evict) = &mut self.on_evict {
+                on_evict(key, val);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use rand::Rng;
+
+    use super::LruCache;
+
+    #[test]
+    fn basics() {
+        let mut cache = LruCache::new(2);
+        assert!(cache.is_empty());
+        assert_eq!(cache.put("a", 1), None);
+        assert_eq!(cache.put("b", 2), None);
+        assert_eq!(cache.get("a"), Some(&1));
+
+        // "b" is the least recently used
+        assert_eq!(cache.put("c", 3), None);
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains("b"));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.peek_lru(), Some((&"a", &1)));
+
+        assert_eq!(cache.put("a", 10), Some(1));
+        assert_eq!(
+            cache.iter().collect::<Vec<_>>(),
+            vec![(&"a", &10), (&"c", &3)]
+        );
+    }
+
+    #[test]
+    fn peek_does_not_refresh() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, "one");
+        cache.put(2, "two");
+        assert_eq!(cache.peek(&1), Some(&"one"));
+        cache.put(3, "three");
+        assert_eq!(cache.peek(&1), None);
+
+        if let Some(val) = cache.get_mut(&2) {
+            *val = "deux";
+        }
+        cache.put(4, "four");
+        assert_eq!(cache.peek(&2), Some(&"deux"));
+        assert_eq!(cache.peek(&3), None);
+    }
+
+    #[test]
+    fn remove_and_pop() {
+        let mut cache = LruCache::new(3);
+        cache.put(String::from("a"), 1);
+        cache.put(String::from("b"), 2);
+        cache.put(String::from("c"), 3);
+        assert_eq!(cache.remove("b"), Some(2));
+        assert_eq!(cache.remove("b"), None);
+        assert_eq!(cache.pop_lru(), Some((String::from("a"), 1)));
+        assert_eq!(cache.pop_lru(), Some((String::from("c"), 3)));
+        assert_eq!(cache.pop_lru(), None);
+
+        cache.put(String::from("d"), 4);
+        cache.clear();
+        assert!(cache.is_empty());
+        assert_eq!(cache.get("d"), None);
+    }
+
+    #[test]
+    fn eviction_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let log = evicted.clone();
+        let mut cache = LruCache::new(3)
+            .with_eviction_callback(move |key, val| log.borrow_mut().push((key, val)));
+
+        for i in 0..5 {
+            cache.put(i, i * 10);
+        }
+        assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10)]);
+
+        cache.get(&2);
+        cache.resize(1);
+        assert_eq!(cache.capacity(), 1);
+        assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10), (3, 30), (4, 40)]);
+        assert_eq!(cache.peek(&2), Some(&20));
+
+        // explicit removals are not evictions
+        cache.pop_lru();
+        cache.resize(2);
+        cache.put(5, 50);
+        cache.put(6, 60);
+        assert_eq!(evicted.borrow().len(), 4);
+    }
+
+    #[test]
+    fn random_against_naive() {
+        let mut rng = rand::thread_rng();
+        let capacity = 16;
+        let mut cache = LruCache::new(capacity);
+        // most recently used first
+        let mut oracle: Vec<(u32, u32)> = Vec::new();
+
+        for _ in 0..5000 {
+            let key = rng.gen_range(0..40);
+            let pos = oracle.iter().position(|&(k, _)| k == key);
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let val = rng.gen();
+                    let prev = pos.map(|pos| oracle.remove(pos).1);
+                    assert_eq!(cache.put(key, val), prev);
+                    oracle.insert(0, (key, val));
+                    oracle.truncate(capacity);
+                }
+                2 => {
+                    let entry = pos.map(|pos| oracle.remove(pos));
+                    assert_eq!(cache.get(&key), entry.map(|(_, v)| v).as_ref());
+                    if let Some(entry) = entry {
+                        oracle.insert(0, entry);
+                    }
+                }
+                _ => {
+                    assert_eq!(cache.peek(&key), pos.map(|pos| &oracle[pos].1));
+                }
+            }
+            assert_eq!(cache.len(), oracle.len());
+        }
+        assert!(cache
+            .iter()
+            .map(|(&k, &v)| (k, v))
+            .eq(oracle.iter().copied()));
+    }
+}
@@ -1,7 +1,25 @@
 pub mod binary_heap;
+pub mod dary_heap;
 pub mod disjoint_set;
+pub mod fibonacci_heap;
+pub mod indexed_binary_heap;
+pub mod keyed_disjoint_set;
 pub mod linked_list;
+pub mod lru_cache;
+pub mod pairing_heap;
+pub mod priority_queue;
+pub mod rollback_disjoint_set;
+pub mod weighted_disjoint_set;
 
-pub use binary_heap::BinaryHeap;
-pub use disjoint_set::DisjointSet;
+pub use binary_heap::{BinaryHeap, Compare, KeyOrder, MaxOrder, MinOrder};
+pub use dary_heap::DaryHeap;
+pub use disjoint_set::{DisjointSet, UnionPolicy};
+pub use fibonacci_heap::FibonacciHeap;
+pub use indexed_binary_heap::IndexedBinaryHeap;
+pub use keyed_disjoint_set::KeyedDisjointSet;
 pub use linked_list::LinkedList;
+pub use lru_cache::LruCache;
+pub use pairing_heap::PairingHeap;
+pub use priority_queue::{DecreaseKey, PriorityQueue};
+pub use rollback_disjoint_set::RollbackDisjointSet;
+pub use weighted_disjoint_set::{AbelianGroup, Conflict, WeightedDisjointSet};
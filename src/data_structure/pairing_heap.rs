@@ -0,0 +1,414 @@
+use std::{
+    cell::{Ref, RefCell},
+    fmt::{self, Debug},
+    rc::{Rc, Weak},
+};
+
+use crate::data_structure::{DecreaseKey, PriorityQueue};
+
+type Link<T> = Rc<RefCell<Node<T>>>;
+
+// left-child right-sibling layout: a node owns its leftmost child and its right sibling,
+// and points back to its left sibling, or to its parent if it is the leftmost child
+struct Node<T> {
+    val: T,
+    child: Option<Link<T>>,
+    next: Option<Link<T>>,
+    prev: Weak<RefCell<Node<T>>>,
+}
+
+/// Reference to an element pushed in a [`PairingHeap`]
+///
+/// The handle becomes invalid once the element is popped or deleted.
+pub struct Handle<T>(Weak<RefCell<Node<T>>>);
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Handle<T> {
+    pub fn is_valid(&self) -> bool {
+        self.0.strong_count() > 0
+    }
+}
+
+/// Pairing min-heap implementation
+///
+/// A heap-ordered tree where push, meld and decrease_key only link two trees in O(1),
+/// all the work being delayed to pop which pairs up the children of the root (amortized O(log n)).
+pub struct PairingHeap<T: Ord> {
+    root: Option<Link<T>>,
+    len: usize,
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Debug> Debug for PairingHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PairingHeap")
+            .field("min", &self.peek())
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub const fn new() -> Self {
+        Self { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        self.root
+            .as_ref()
+            .map(|root| Ref::map(root.borrow(), |node| &node.val))
+    }
+
+    pub fn push(&mut self, val: T) -> Handle<T> {
+        let node = Rc::new(RefCell::new(Node {
+            val,
+            child: None,
+            next: None,
+            prev: Weak::new(),
+        }));
+        let handle = Handle(Rc::downgrade(&node));
+        self.root = Self::link(self.root.take(), Some(node));
+        self.len += 1;
+        handle
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        let child = root.borrow_mut().child.take();
+        self.root = Self::merge_pairs(child);
+        self.len -= 1;
+        Some(Self::into_val(root))
+    }
+
+    /// Move all the elements of `other` into this heap in O(1)
+    ///
+    /// The handles of `other` stay valid and now refer to this heap.
+    pub fn meld(&mut self, mut other: Self) {
+        self.root = Self::link(self.root.take(), other.root.take());
+        self.len += other.len;
+        other.len = 0;
+    }
+
+    /// Lower the value of the element behind `handle`
+    ///
+    /// Returns false if the handle is no longer valid or if `new` is not smaller than the current value.
+    /// The handle must come from this heap.
+    pub fn decrease_key(&mut self, handle: &Handle<T>, new: T) -> bool {
+        let Some(node) = handle.0.upgrade() else {
+            return false;
+        };
+        if new >= node.borrow().val {
+            return false;
+        }
+        node.borrow_mut().val = new;
+
+        // the root has no parent and cannot violate the heap order
+        if Self::cut(&node) {
+            self.root = Self::link(self.root.take(), Some(node));
+        }
+        true
+    }
+
+    /// Remove the element behind `handle` from the heap
+    ///
+    /// Returns `None` if the handle is no longer valid. The handle must come from this heap.
+    pub fn delete(&mut self, handle: &Handle<T>) -> Option<T> {
+        let node = handle.0.upgrade()?;
+        if !Self::cut(&node) {
+            // this is the root
+            drop(node);
+            return self.pop();
+        }
+        let child = node.borrow_mut().child.take();
+        self.root = Self::link(self.root.take(), Self::merge_pairs(child));
+        self.len -= 1;
+        Some(Self::into_val(node))
+    }
+
+    /// Detach `node` and its subtree from the tree in O(1), returns false if it is the root
+    fn cut(node: &Link<T>) -> bool {
+        let Some(prev) = node.borrow().prev.upgrade() else {
+            return false;
+        };
+        let next = node.borrow_mut().next.take();
+        if let Some(next) = &next {
+            next.borrow_mut().prev = Rc::downgrade(&prev);
+        }
+
+        let mut prev = prev.borrow_mut();
+        let is_leftmost = prev
+            .child
+            .as_ref()
+            .is_some_and(|child| Rc::ptr_eq(child, node));
+        if is_leftmost {
+            prev.child = next;
+        } else {
+            prev.next = next;
+        }
+        node.borrow_mut().prev = Weak::new();
+        true
+    }
+
+    /// The root with the larger value becomes a child of the other
+    fn link(a: Option<Link<T>>, b: Option<Link<T>>) -> Option<Link<T>> {
+        match (a, b) {
+            (None, b) => b,
+            (a, None) => a,
+            (Some(a), Some(b)) => {
+                let (parent, child) = if b.borrow().val < a.borrow().val {
+                    (b, a)
+                } else {
+                    (a, b)
+                };
+                // the child becomes the leftmost one
+                let mut parent_node = parent.borrow_mut();
+                if let Some(first) = &parent_node.child {
+                    first.borrow_mut().prev = Rc::downgrade(&child);
+                }
+                {
+                    let mut child_node = child.borrow_mut();
+                    child_node.next = parent_node.child.take();
+                    child_node.prev = Rc::downgrade(&parent);
+                }
+                parent_node.child = Some(child);
+                drop(parent_node);
+                Some(parent)
+            }
+        }
+    }
+
+    /// Two-pass pairing of the subtrees of a removed root, given its leftmost child
+    fn merge_pairs(first: Option<Link<T>>) -> Option<Link<T>> {
+        // detach the siblings from each other
+        let mut children = Vec::new();
+        let mut cur = first;
+        while let Some(child) = cur {
+            {
+                let mut node = child.borrow_mut();
+                cur = node.next.take();
+                node.prev = Weak::new();
+            }
+            children.push(child);
+        }
+
+        // first pass: link the children two by two, from left to right
+        let mut pairs = Vec::with_capacity(children.len().div_ceil(2));
+        let mut iter = children.into_iter();
+        while let Some(a) = iter.next() {
+            pairs.push(Self::link(Some(a), iter.next()));
+        }
+
+        // second pass: link the pairs into a single tree, from right to left
+        pairs
+            .into_iter()
+            .rev()
+            .fold(None, |acc, pair| Self::link(pair, acc))
+    }
+
+    fn into_val(node: Link<T>) -> T {
+        match Rc::try_unwrap(node) {
+            Ok(node) => node.into_inner().val,
+            Err(_) => unreachable!("detached nodes are only referenced by weak handles"),
+        }
+    }
+}
+
+impl<T: Ord> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        // a degenerate tree may be as deep as the number of elements,
+        // unlink the nodes iteratively to avoid a recursive drop overflowing the stack
+        let mut stack: Vec<Link<T>> = self.root.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let mut node = node.borrow_mut();
+            stack.extend(node.child.take());
+            stack.extend(node.next.take());
+        }
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for PairingHeap<T> {
+    type Handle = Handle<T>;
+
+    fn push(&mut self, val: T) -> Handle<T> {
+        PairingHeap::push(self, val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        PairingHeap::len(self)
+    }
+}
+
+impl<T: Ord> DecreaseKey<T> for PairingHeap<T> {
+    fn decrease_key(&mut self, handle: &Handle<T>, new: T) -> bool {
+        PairingHeap::decrease_key(self, handle, new)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PairingHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for PairingHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::PairingHeap;
+
+    #[test]
+    fn test_empty_heap() {
+        let mut heap: PairingHeap<usize> = PairingHeap::new();
+        assert_eq!(heap.pop(), None);
+        assert!(heap.peek().is_none());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap = PairingHeap::new();
+        heap.push(4);
+        heap.push(2);
+        heap.push(9);
+        heap.push(11);
+        assert_eq!(heap.len(), 4);
+        assert_eq!(*heap.peek().unwrap(), 2);
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), Some(9));
+        heap.push(1);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(11));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut a: PairingHeap<_> = [5, 1, 8].into_iter().collect();
+        let mut b = PairingHeap::new();
+        let h = b.push(7);
+        b.extend([0, 3]);
+
+        a.meld(b);
+        assert_eq!(a.len(), 6);
+        // handles survive the meld
+        assert!(a.decrease_key(&h, -1));
+        assert_eq!(a.pop(), Some(-1));
+        assert_eq!(a.pop(), Some(0));
+        assert_eq!(a.pop(), Some(1));
+        assert_eq!(a.pop(), Some(3));
+        assert_eq!(a.pop(), Some(5));
+        assert_eq!(a.pop(), Some(8));
+        assert_eq!(a.pop(), None);
+    }
+
+    #[test]
+    fn test_decrease_key_and_delete() {
+        let mut heap = PairingHeap::new();
+        let handles = (0..10).map(|i| heap.push(i * 10)).collect::<Vec<_>>();
+        // force some tree structure
+        assert_eq!(heap.pop(), Some(0));
+        assert!(!handles[0].is_valid());
+        assert!(!heap.decrease_key(&handles[0], -5));
+
+        assert!(heap.decrease_key(&handles[7], 5));
+        assert!(!heap.decrease_key(&handles[3], 35));
+        assert_eq!(heap.delete(&handles[1]), Some(10));
+        assert_eq!(heap.delete(&handles[1]), None);
+        assert_eq!(heap.delete(&handles[7]), Some(5));
+
+        assert_eq!(heap.len(), 7);
+        let mut popped = Vec::new();
+        while let Some(val) = heap.pop() {
+            popped.push(val);
+        }
+        assert_eq!(popped, vec![20, 30, 40, 50, 60, 80, 90]);
+    }
+
+    #[test]
+    fn test_random_against_sorted() {
+        let mut rng = rand::thread_rng();
+        let mut heap = PairingHeap::new();
+        let mut oracle = Vec::new();
+        let mut handles = Vec::new();
+        for _ in 0..3000 {
+            let val = rng.gen_range(0..100_000);
+            handles.push((heap.push(val), val));
+        }
+        for (handle, val) in handles.iter_mut().take(1000) {
+            let new = rng.gen_range(0..=*val);
+            assert_eq!(heap.decrease_key(handle, new), new < *val);
+            *val = new;
+        }
+        for (i, (handle, val)) in handles.into_iter().enumerate() {
+            if i % 7 == 0 {
+                assert_eq!(heap.delete(&handle), Some(val));
+            } else {
+                oracle.push(val);
+            }
+        }
+        oracle.sort();
+
+        assert_eq!(heap.len(), oracle.len());
+        for expected in oracle {
+            assert_eq!(heap.pop(), Some(expected));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_decrease_key_wide_root() {
+        // increasing values hang every element off the root, each cut must not scan its siblings
+        let mut heap = PairingHeap::new();
+        let n = 100_000;
+        let handles = (0..n).map(|i| heap.push(i + n)).collect::<Vec<_>>();
+        for (i, handle) in handles.iter().enumerate().skip(1) {
+            assert!(heap.decrease_key(handle, n - i));
+        }
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.len(), n - 2);
+    }
+
+    #[test]
+    fn test_deep_drop() {
+        // decreasing values build a single chain
+        let mut heap = PairingHeap::new();
+        for i in (0..200_000).rev() {
+            heap.push(i);
+        }
+        drop(heap);
+    }
+}
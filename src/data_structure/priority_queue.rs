@@ -0,0 +1,86 @@
+use crate::data_structure::{binary_heap::Compare, BinaryHeap, DaryHeap};
+
+/// Common interface of the heaps, the top element is popped first
+///
+/// This allows algorithms such as Dijkstra or Prim to run on any of the heaps of this crate.
+pub trait PriorityQueue<T> {
+    /// Reference to a pushed element, used by the heaps supporting decrease_key
+    type Handle;
+
+    fn push(&mut self, val: T) -> Self::Handle;
+
+    fn pop(&mut self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Priority queue whose elements can be lowered in place through the handle returned by push
+pub trait DecreaseKey<T>: PriorityQueue<T> {
+    /// Returns false if the handle is no longer valid or if `new` does not come strictly before the current value
+    fn decrease_key(&mut self, handle: &Self::Handle, new: T) -> bool;
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for BinaryHeap<T, C> {
+    type Handle = ();
+
+    fn push(&mut self, val: T) {
+        BinaryHeap::push(self, val);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> PriorityQueue<T> for DaryHeap<T, D, C> {
+    type Handle = ();
+
+    fn push(&mut self, val: T) {
+        DaryHeap::push(self, val);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        DaryHeap::pop(self)
+    }
+
+    fn len(&self) -> usize {
+        DaryHeap::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecreaseKey;
+    use crate::data_structure::{FibonacciHeap, PairingHeap};
+
+    fn check_decrease_key<Q: DecreaseKey<i32> + Default>() {
+        let mut heap = Q::default();
+        let a = heap.push(10);
+        heap.push(20);
+        let c = heap.push(30);
+
+        assert!(!heap.decrease_key(&c, 30));
+        assert!(!heap.decrease_key(&c, 40));
+        assert!(heap.decrease_key(&c, 5));
+        assert!(!heap.decrease_key(&c, 5));
+        assert!(!heap.decrease_key(&a, 10));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.pop(), Some(10));
+        assert_eq!(heap.pop(), Some(20));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_equal_key_refused() {
+        check_decrease_key::<PairingHeap<i32>>();
+        check_decrease_key::<FibonacciHeap<i32>>();
+    }
+}
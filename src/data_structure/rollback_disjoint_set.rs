@@ -0,0 +1,203 @@
+/// Disjoint set whose unions can be undone
+///
+/// Path compression would rewrite parents outside of the unions, so `find` only walks up the trees.
+/// Union by rank keeps them O(log n) deep, and every union is recorded so it can be rolled back.
+#[derive(Debug, Clone)]
+pub struct RollbackDisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    // number of elements of the set, only valid for roots
+    size: Vec<usize>,
+    count: usize,
+    history: Vec<Merge>,
+}
+
+/// A union that can be undone: `child` was attached under `parent`
+#[derive(Debug, Clone, Copy)]
+struct Merge {
+    child: usize,
+    parent: usize,
+    rank_increased: bool,
+}
+
+impl RollbackDisjointSet {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            size: vec![1; size],
+            count: size,
+            history: Vec::new(),
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merge the sets of `x` and `y`, returns false if they were already in the same set
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let root_x = self.find(x);
+        let root_y = self.find(y);
+        if root_x == root_y {
+            return false;
+        }
+
+        // the larger tree becomes the parent
+        let (parent, child) = if self.rank[root_x] >= self.rank[root_y] {
+            (root_x, root_y)
+        } else {
+            (root_y, root_x)
+        };
+        let rank_increased = self.rank[parent] == self.rank[child];
+        if rank_increased {
+            self.rank[parent] += 1;
+        }
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        self.count -= 1;
+        self.history.push(Merge {
+            child,
+            parent,
+            rank_increased,
+        });
+        true
+    }
+
+    pub fn same_set(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of elements in the set of `x`
+    pub fn size_of(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    /// Number of disjoint sets
+    pub fn count_sets(&self) -> usize {
+        self.count
+    }
+
+    /// Current state, that can be restored later with [`rollback`](Self::rollback)
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undo all the unions made since `snapshot` was taken
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snapshot` is more recent than the current state,
+    /// i.e. if the unions it was taken after were already rolled back.
+    pub fn rollback(&mut self, snapshot: usize) {
+        assert!(
+            snapshot <= self.history.len(),
+            "cannot roll back to a more recent snapshot"
+        );
+        // undo the unions in reverse order
+        while self.history.len() > snapshot {
+            let Some(merge) = self.history.pop() else {
+                break;
+            };
+            self.parent[merge.child] = merge.child;
+            self.size[merge.parent] -= self.size[merge.child];
+            if merge.rank_increased {
+                self.rank[merge.parent] -= 1;
+            }
+            self.count += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::RollbackDisjointSet;
+    use crate::data_structure::DisjointSet;
+
+    #[test]
+    fn test_union_and_rollback() {
+        let mut ds = RollbackDisjointSet::new(6);
+        assert!(ds.union(0, 1));
+        let snap = ds.snapshot();
+
+        assert!(ds.union(2, 3));
+        assert!(ds.union(1, 3));
+        assert!(!ds.union(0, 2));
+        assert_eq!(ds.count_sets(), 3);
+        assert_eq!(ds.size_of(2), 4);
+        assert!(ds.same_set(0, 3));
+
+        ds.rollback(snap);
+        assert_eq!(ds.count_sets(), 5);
+        assert_eq!(ds.size_of(0), 2);
+        assert_eq!(ds.size_of(2), 1);
+        assert!(ds.same_set(0, 1));
+        assert!(!ds.same_set(0, 3));
+        assert!(!ds.same_set(2, 3));
+
+        ds.rollback(0);
+        assert_eq!(ds.count_sets(), 6);
+        assert!(!ds.same_set(0, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rollback_to_future() {
+        let mut ds = RollbackDisjointSet::new(3);
+        ds.union(0, 1);
+        let snap = ds.snapshot();
+        ds.rollback(0);
+        ds.rollback(snap);
+    }
+
+    #[test]
+    fn test_nested_rollbacks_against_rebuild() {
+        let mut rng = rand::thread_rng();
+        let n = 50;
+        let mut ds = RollbackDisjointSet::new(n);
+        // unions applied so far, with the snapshots taken between them
+        let mut unions = Vec::new();
+        let mut snapshots = Vec::new();
+
+        for _ in 0..500 {
+            match rng.gen_range(0..5) {
+                0 => snapshots.push((ds.snapshot(), unions.len())),
+                1 if !snapshots.is_empty() => {
+                    let (snap, n_unions) = snapshots.pop().unwrap();
+                    ds.rollback(snap);
+                    unions.truncate(n_unions);
+                }
+                _ => {
+                    let (x, y) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                    ds.union(x, y);
+                    unions.push((x, y));
+                }
+            }
+
+            // compare with a disjoint set built from scratch
+            let mut expected = DisjointSet::new(n);
+            for &(x, y) in &unions {
+                expected.union(x, y);
+            }
+            assert_eq!(ds.count_sets(), expected.count_sets());
+            for x in 0..n {
+                assert_eq!(ds.size_of(x), expected.size_of(x));
+                assert_eq!(ds.same_set(0, x), expected.same_set(0, x));
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::{error::Error, fmt, fmt::Debug};
+
+/// Values that can be added, subtracted and reordered freely
+///
+/// The differences between elements of a [`WeightedDisjointSet`] live in such a group.
+pub trait AbelianGroup: Copy + PartialEq + Debug {
+    fn zero() -> Self;
+
+    fn add(self, other: Self) -> Self;
+
+    fn neg(self) -> Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.add(other.neg())
+    }
+}
+
+macro_rules! impl_int_group {
+    ($($t:ty),*) => {
+        $(
+            impl AbelianGroup for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn add(self, other: Self) -> Self {
+                    self + other
+                }
+
+                fn neg(self) -> Self {
+                    -self
+                }
+            }
+        )*
+    };
+}
+
+impl_int_group!(i8, i16, i32, i64, i128, isize);
+
+/// Integers modulo 2, used for parity constraints such as "x and y have different colors"
+impl AbelianGroup for bool {
+    fn zero() -> Self {
+        false
+    }
+
+    fn add(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+/// A constraint contradicting the ones already known
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict<W> {
+    pub x: usize,
+    pub y: usize,
+    /// Difference between `x` and `y` implied by the previous constraints
+    pub known: W,
+    /// Difference between `x` and `y` that was requested
+    pub requested: W,
+}
+
+impl<W: Debug> fmt::Display for Conflict<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "conflicting constraint: {} - {} = {:?}, but it is already known to be {:?}",
+            self.x, self.y, self.requested, self.known
+        )
+    }
+}
+
+impl<W: Debug> Error for Conflict<W> {}
+
+/// Disjoint set keeping track of the differences between the elements of a set
+///
+/// Every element stores its difference with its parent, so that the difference
+/// with the root is the sum along the path. Two elements can only be compared if they are in the same set.
+pub struct WeightedDisjointSet<W> {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    // value of the element minus the value of its parent
+    weight: Vec<W>,
+    count: usize,
+}
+
+impl<W: AbelianGroup> WeightedDisjointSet<W> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            weight: vec![W::zero(); size],
+            count: size,
+        }
+    }
+
+    /// Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        self.find_with_diff(x).0
+    }
+
+    pub fn same_set(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of disjoint sets
+    pub fn count_sets(&self) -> usize {
+        self.count
+    }
+
+    /// Value of `x` minus the value of `y`, `None` if they are not related
+    pub fn diff(&mut self, x: usize, y: usize) -> Option<W> {
+        let (root_x, diff_x) = self.find_with_diff(x);
+        let (root_y, diff_y) = self.find_with_diff(y);
+        (root_x == root_y).then(|| diff_x.sub(diff_y))
+    }
+
+    /// Record that the value of `x` minus the value of `y` is `diff`
+    ///
+    /// Fails if `x` and `y` are already related with a different difference, the set is left unchanged.
+    pub fn union_with(&mut self, x: usize, y: usize, diff: W) -> Result<(), Conflict<W>> {
+        let (root_x, diff_x) = self.find_with_diff(x);
+        let (root_y, diff_y) = self.find_with_diff(y);
+        if root_x == root_y {
+            let known = diff_x.sub(diff_y);
+            if known != diff {
+                return Err(Conflict {
+                    x,
+                    y,
+                    known,
+                    requested: diff,
+                });
+            }
+            return Ok(());
+        }
+
+        // value(root_y) - value(root_x), from value(x) - value(y) = diff
+        let roots_diff = diff_x.sub(diff).sub(diff_y);
+        // union by rank
+        if self.rank[root_x] >= self.rank[root_y] {
+            self.parent[root_y] = root_x;
+            self.weight[root_y] = roots_diff;
+            if self.rank[root_x] == self.rank[root_y] {
+                self.rank[root_x] += 1;
+            }
+        } else {
+            self.parent[root_x] = root_y;
+            self.weight[root_x] = roots_diff.neg();
+        }
+        self.count -= 1;
+        Ok(())
+    }
+
+    /// Root of `x` and the value of `x` minus the value of the root
+    fn find_with_diff(&mut self, x: usize) -> (usize, W) {
+        let mut root = x;
+        let mut total = W::zero();
+        while self.parent[root] != root {
+            total = total.add(self.weight[root]);
+            root = self.parent[root];
+        }
+
+        // path compression: link every node of the path to the root,
+        // its difference with the root is what remains of the total
+        let mut node = x;
+        let mut remaining = total;
+        while self.parent[node] != node {
+            let next = self.parent[node];
+            let weight = self.weight[node];
+            self.parent[node] = root;
+            self.weight[node] = remaining;
+            remaining = remaining.sub(weight);
+            node = next;
+        }
+        (root, total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::{Conflict, WeightedDisjointSet};
+
+    #[test]
+    fn test_offsets() {
+        let mut ds = WeightedDisjointSet::new(5);
+        // 1 is 5 more than 0, 2 is 3 less than 1
+        ds.union_with(1, 0, 5).unwrap();
+        ds.union_with(2, 1, -3).unwrap();
+        ds.union_with(4, 3, 10).unwrap();
+        assert_eq!(ds.diff(2, 0), Some(2));
+        assert_eq!(ds.diff(0, 2), Some(-2));
+        assert_eq!(ds.diff(0, 0), Some(0));
+        assert_eq!(ds.diff(0, 3), None);
+        assert_eq!(ds.count_sets(), 2);
+
+        ds.union_with(3, 2, 1).unwrap();
+        assert_eq!(ds.diff(4, 0), Some(13));
+        assert!(ds.same_set(0, 4));
+        assert_eq!(ds.count_sets(), 1);
+
+        // consistent constraints are accepted again
+        assert_eq!(ds.union_with(4, 1, 8), Ok(()));
+        assert_eq!(
+            ds.union_with(4, 1, 7),
+            Err(Conflict {
+                x: 4,
+                y: 1,
+                known: 8,
+                requested: 7
+            })
+        );
+        assert_eq!(ds.diff(4, 1), Some(8));
+    }
+
+    #[test]
+    fn test_parity() {
+        // an odd cycle cannot be colored with two colors
+        let mut ds = WeightedDisjointSet::new(5);
+        for (x, y) in [(0, 1), (1, 2), (2, 3)] {
+            ds.union_with(x, y, true).unwrap();
+        }
+        assert_eq!(ds.diff(0, 2), Some(false));
+        assert_eq!(ds.diff(0, 3), Some(true));
+        assert!(ds.union_with(3, 0, true).is_ok());
+        assert!(ds.union_with(2, 0, true).is_err());
+    }
+
+    #[test]
+    fn test_random_hidden_values() {
+        let mut rng = rand::thread_rng();
+        let n = 200;
+        let values = (0..n)
+            .map(|_| rng.gen_range(-1000..1000))
+            .collect::<Vec<i64>>();
+        let mut ds = WeightedDisjointSet::new(n);
+        for _ in 0..1000 {
+            let (x, y) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            if rng.gen_bool(0.3) {
+                ds.union_with(x, y, values[x] - values[y]).unwrap();
+            } else if let Some(diff) = ds.diff(x, y) {
+                assert_eq!(diff, values[x] - values[y]);
+                if x != y {
+                    assert!(ds.union_with(x, y, diff + 1).is_err());
+                }
+            }
+        }
+    }
+}
@@ -1,141 +1,77 @@
-use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::cmp::Reverse;
 
-pub trait NodeVal: Ord + Copy + Default {}
-impl<T: Ord + Copy + Default> NodeVal for T {}
+use crate::graph::{
+    frontier::{Frontier, NodeQueue},
+    Graph, NodeId, NodeVal, QueueStrategy, ShortestPathTree, Weight,
+};
 
-pub type NodeId = usize;
-
-#[derive(Default)]
-pub struct Graph<V: NodeVal> {
-    pub nodes: Vec<V>,
-    pub edges: HashMap<NodeId, Vec<Edge>>,
-}
-
-pub struct Edge {
-    #[allow(dead_code)]
+pub fn astar<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
     src: NodeId,
-    dst: NodeId,
-    cost: usize,
-}
-
-#[derive(Debug, Eq, PartialEq)]
-pub struct QueueEntry {
-    real_cost: usize,
-    heur_cost: usize,
-    node_id: NodeId,
-}
-
-impl PartialOrd for QueueEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for QueueEntry {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // NOTE: we reverse the order to get smaller cost at the top
-        other.heur_cost.cmp(&self.heur_cost)
-    }
-}
-
-impl<N: NodeVal> Graph<N> {
-    pub fn edges(&self, node_id: NodeId) -> &[Edge] {
-        if let Some(e) = self.edges.get(&node_id) {
-            e
-        } else {
-            &[]
-        }
-    }
-
-    #[allow(dead_code)]
-    fn add_edge(&mut self, src: NodeId, dst: NodeId, cost: usize) {
-        self.edges
-            .entry(src)
-            .or_default()
-            .push(Edge { src, dst, cost });
-    }
+    target: V,
+    heuristic: impl Fn(&Graph<V, W>, NodeId) -> W,
+) -> Option<(W, Vec<NodeId>)> {
+    astar_with(graph, src, target, heuristic, QueueStrategy::default())
 }
 
-pub fn astar<V: NodeVal>(
-    graph: &Graph<V>,
+/// A* with a choice of priority queue
+pub fn astar_with<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
     src: NodeId,
     target: V,
-    heuristic: impl Fn(&Graph<V>, NodeId) -> usize,
-) -> Option<(usize, Vec<NodeId>)> {
-    let mut prevs = BTreeMap::new();
-    let mut queue = BinaryHeap::new();
-
-    queue.push(QueueEntry {
-        real_cost: 0,
-        heur_cost: heuristic(graph, src),
-        node_id: src,
-    });
-    prevs.insert(src, (None, 0));
+    heuristic: impl Fn(&Graph<V, W>, NodeId) -> W,
+    strategy: QueueStrategy,
+) -> Option<(W, Vec<NodeId>)> {
+    let mut prevs = ShortestPathTree::new();
+    let mut queue = Frontier::new(strategy);
+
+    // nodes are ordered by estimated total cost,
+    // on ties the node with the largest real cost is the closest to the target
+    queue.push(src, (heuristic(graph, src), Reverse(W::zero())));
+    prevs.insert(src, None, W::zero());
 
     let mut found = None;
-    while let Some(e) = queue.pop() {
-        let nval = graph.nodes[e.node_id];
+    while let Some((node_id, (_, Reverse(real_cost)))) = queue.pop() {
+        // NOTE: with lazy deletion, duplicated nodes are pushed instead of using decrease_key,
+        // the outdated entries are skipped
+        if prevs.cost_to(node_id).is_some_and(|c| real_cost > c) {
+            continue;
+        }
+        let nval = graph.nodes[node_id];
         if nval == target {
-            found = Some((e.node_id, e.real_cost));
+            found = Some((node_id, real_cost));
             break;
         }
-        for edge in graph.edges(e.node_id) {
-            if edge.dst == src {
+        for edge in graph.edges(node_id) {
+            if edge.dst() == src {
                 continue;
             }
-            let new_rcost = e.real_cost + edge.cost;
-
-            // NOTE: the ideal solution would be to update the cost of the existing nodes in the priority queue
-            // using decrease_key. However, Rust's BinaryHeap does not support this operation.
-            // We accept the duplication of nodes in the priority queue as a tradeoff
-            if prevs.get(&edge.dst).is_none_or(|(_, c)| new_rcost < *c) {
-                let new_hcost = new_rcost + heuristic(graph, edge.dst);
-                prevs.insert(edge.dst, (Some(e.node_id), new_rcost));
-                queue.push(QueueEntry {
-                    real_cost: new_rcost,
-                    heur_cost: new_hcost,
-                    node_id: edge.dst,
-                });
+            let new_rcost = real_cost.add(edge.cost());
+
+            if prevs.cost_to(edge.dst()).is_none_or(|c| new_rcost < c) {
+                let new_hcost = new_rcost.add(heuristic(graph, edge.dst()));
+                prevs.insert(edge.dst(), Some(node_id), new_rcost);
+                queue.push(edge.dst(), (new_hcost, Reverse(new_rcost)));
             }
         }
     }
 
-    // reconstruct path
     let (target_id, target_cost) = found?;
-    let mut path = vec![target_id];
-    let mut cur = target_id;
-    while cur != src {
-        let (prev_node, _) = prevs[&cur];
-        cur = prev_node?;
-        path.push(cur);
-    }
-    path.reverse();
-    Some((target_cost, path))
+    Some((target_cost, prevs.path_to(target_id)?))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{astar, Graph, NodeVal};
+    use super::{astar, astar_with, Graph, NodeVal};
+    use crate::graph::{example_graph, QueueStrategy};
 
     fn null_heuristic<V: NodeVal>(_: &Graph<V>, _: usize) -> usize {
         0
     }
 
-    // https://imagedelivery.net/CLfkmk9Wzy8_9HRyug4EVA/482027d5-fb4e-4a3c-d710-ec60cbead600/sharpen=1
     #[test]
     fn test_dijkstra() {
-        let mut graph = Graph::default();
-        for i in 0..5 {
-            graph.nodes.push(i);
-        }
-
-        graph.add_edge(0, 1, 10);
-        graph.add_edge(0, 2, 3);
-        graph.add_edge(1, 3, 2);
-        graph.add_edge(2, 1, 4);
-        graph.add_edge(2, 3, 8);
-        graph.add_edge(2, 4, 2);
-        graph.add_edge(3, 4, 5);
+        let graph = example_graph();
 
         assert_eq!(
             astar(&graph, 0, 1, null_heuristic),
@@ -150,6 +86,18 @@ mod tests {
             astar(&graph, 0, 4, null_heuristic),
             Some((5, vec![0, 2, 4]))
         );
+        for target in 0..5 {
+            assert_eq!(
+                astar_with(
+                    &graph,
+                    0,
+                    target,
+                    null_heuristic,
+                    QueueStrategy::DecreaseKey
+                ),
+                astar(&graph, 0, target, null_heuristic)
+            );
+        }
     }
 
     #[test]
@@ -0,0 +1,210 @@
+use std::{collections::BTreeMap, error::Error, fmt};
+
+use crate::graph::{Graph, NodeId, NodeVal, ShortestPathTree, Weight};
+
+/// A cycle whose total cost is negative, given as the sequence of nodes along the cycle
+///
+/// The last node has an edge going back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NegativeCycle {
+    pub cycle: Vec<NodeId>,
+}
+
+impl fmt::Display for NegativeCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "negative cycle:")?;
+        for node_id in &self.cycle {
+            write!(f, " {node_id} ->")?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, " {first}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for NegativeCycle {}
+
+/// Bellman-Ford single source shortest paths
+///
+/// Unlike Dijkstra, negative edge weights are supported.
+/// If a negative cycle is reachable from `src`, there is no shortest path and the cycle is returned.
+pub fn bellman_ford<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    src: NodeId,
+) -> Result<ShortestPathTree<W>, NegativeCycle> {
+    let mut prevs = BTreeMap::new();
+    prevs.insert(src, (None, W::zero()));
+
+    // a shortest path has at most n - 1 edges, so n - 1 rounds of relaxation are enough
+    run(graph, prevs, graph.nodes.len().saturating_sub(1)).map(ShortestPathTree::from)
+}
+
+/// Shortest distance from a virtual node linked to every node with a zero cost edge
+///
+/// These are the potentials used by Johnson's algorithm to get rid of negative edges.
+pub(crate) fn potentials<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+) -> Result<Vec<W>, NegativeCycle> {
+    let prevs = (0..graph.nodes.len())
+        .map(|node_id| (node_id, (None, W::zero())))
+        .collect();
+
+    // the virtual node adds one more node to the graph, hence n rounds
+    let prevs = run(graph, prevs, graph.nodes.len())?;
+    Ok(prevs.into_values().map(|(_, cost)| cost).collect())
+}
+
+fn run<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    mut prevs: BTreeMap<NodeId, (Option<NodeId>, W)>,
+    rounds: usize,
+) -> Result<BTreeMap<NodeId, (Option<NodeId>, W)>, NegativeCycle> {
+    for _ in 0..rounds {
+        if relax(graph, &mut prevs).is_none() {
+            return Ok(prevs);
+        }
+    }
+
+    // if an edge can still be relaxed, a negative cycle is reachable
+    match relax(graph, &mut prevs) {
+        None => Ok(prevs),
+        Some(node_id) => Err(NegativeCycle {
+            cycle: extract_cycle(rounds + 1, &prevs, node_id),
+        }),
+    }
+}
+
+/// Relax every edge once, returns the last node whose cost was improved
+fn relax<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    prevs: &mut BTreeMap<NodeId, (Option<NodeId>, W)>,
+) -> Option<NodeId> {
+    let mut updated = None;
+    for node_id in 0..graph.nodes.len() {
+        let Some(&(_, cost)) = prevs.get(&node_id) else {
+            // not reached yet
+            continue;
+        };
+        for edge in graph.edges(node_id) {
+            let new_cost = cost.add(edge.cost());
+            if prevs
+                .get(&edge.dst())
+                .is_none_or(|(_, prev_cost)| new_cost < *prev_cost)
+            {
+                prevs.insert(edge.dst(), (Some(node_id), new_cost));
+                updated = Some(edge.dst());
+            }
+        }
+    }
+    updated
+}
+
+fn extract_cycle<W: Weight>(
+    steps: usize,
+    prevs: &BTreeMap<NodeId, (Option<NodeId>, W)>,
+    mut node_id: NodeId,
+) -> Vec<NodeId> {
+    // the updated node may only hang off the cycle,
+    // walking back as many predecessors as there are nodes guarantees that we end up on it
+    for _ in 0..steps {
+        node_id = prevs[&node_id].0.expect("relaxed nodes have a predecessor");
+    }
+
+    let start = node_id;
+    let mut cycle = vec![start];
+    loop {
+        node_id = prevs[&node_id].0.expect("relaxed nodes have a predecessor");
+        if node_id == start {
+            break;
+        }
+        cycle.push(node_id);
+    }
+    // predecessors were followed backwards
+    cycle.reverse();
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{bellman_ford, NegativeCycle};
+    use crate::graph::{dijkstra, example_graph, Graph};
+
+    fn rotate_to_min(mut cycle: Vec<usize>) -> Vec<usize> {
+        let min_idx = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+        cycle.rotate_left(min_idx);
+        cycle
+    }
+
+    #[test]
+    fn same_as_dijkstra() {
+        let graph = example_graph();
+
+        for src in 0..5 {
+            assert_eq!(bellman_ford(&graph, src), Ok(dijkstra(&graph, src)));
+        }
+    }
+
+    #[test]
+    fn negative_weights() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(0, 2, 5);
+        graph.add_edge(2, 1, -3);
+        graph.add_edge(1, 3, 2);
+        graph.add_edge(3, 2, 1);
+
+        let mut expected = BTreeMap::new();
+        expected.insert(0, (None, 0));
+        expected.insert(1, (Some(2), 2));
+        expected.insert(2, (Some(0), 5));
+        expected.insert(3, (Some(1), 4));
+        assert_eq!(bellman_ford(&graph, 0).map(|t| t.into_map()), Ok(expected));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = Graph::new();
+        for i in 0..6 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, 1);
+        graph.add_edge(2, 3, -4);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(4, 2, 1);
+        graph.add_edge(4, 5, 1);
+
+        let err = bellman_ford(&graph, 0).unwrap_err();
+        assert_eq!(rotate_to_min(err.cycle.clone()), vec![2, 3, 4]);
+        assert_eq!(err.to_string(), {
+            let c = &err.cycle;
+            format!(
+                "negative cycle: {} -> {} -> {} -> {}",
+                c[0], c[1], c[2], c[0]
+            )
+        });
+
+        // the cycle is not reachable from node 5
+        assert!(bellman_ford(&graph, 5).is_ok());
+    }
+
+    #[test]
+    fn negative_self_loop() {
+        let mut graph = Graph::new();
+        graph.add_node(0);
+        graph.add_node(1);
+        graph.add_edge(0, 1, 3);
+        graph.add_edge(1, 1, -1);
+
+        assert_eq!(
+            bellman_ford(&graph, 0),
+            Err(NegativeCycle { cycle: vec![1] })
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::graph::{
+    dijkstra::Search, frontier::Frontier, Graph, NodeId, NodeVal, QueueStrategy, Weight,
+};
+
+/// Shortest path from `src` to `dst`, searching forwards from `src` and backwards from `dst` at once
+///
+/// `reverse` must be `graph.reversed()`, built once and reused across queries.
+pub fn bidirectional_dijkstra<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    reverse: &Graph<V, W>,
+    src: NodeId,
+    dst: NodeId,
+) -> Option<(W, Vec<NodeId>)> {
+    bidirectional_dijkstra_with(graph, reverse, src, dst, QueueStrategy::default())
+}
+
+/// Bidirectional Dijkstra with a choice of priority queue for both directions
+///
+/// Every time a node is reached by both searches, the path through it is a candidate.
+/// The side which settled the closer node advances. Once the costs of the last nodes settled
+/// on each side add up to at least the best candidate, no unsettled node can lie on a shorter path.
+pub fn bidirectional_dijkstra_with<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    reverse: &Graph<V, W>,
+    src: NodeId,
+    dst: NodeId,
+    strategy: QueueStrategy,
+) -> Option<(W, Vec<NodeId>)> {
+    let mut forward = Search::new(graph, &[src], Frontier::new(strategy));
+    let mut backward = Search::new(reverse, &[dst], Frontier::new(strategy));
+    // cost of the best path found so far and the node where both searches met on it
+    let mut best = (src == dst).then_some((W::zero(), src));
+    // cost of the last node settled by each side, every queued cost is at least as large
+    let mut forward_reach = W::zero();
+    let mut backward_reach = W::zero();
+
+    while best.is_none_or(|(cost, _)| forward_reach.add(backward_reach) < cost) {
+        let (search, other, reach) = if forward_reach <= backward_reach {
+            (&mut forward, &backward, &mut forward_reach)
+        } else {
+            (&mut backward, &forward, &mut backward_reach)
+        };
+        // once a side has settled everything it reaches, every meeting point was seen
+        let Some((node_id, cost)) = search.settle() else {
+            break;
+        };
+        *reach = cost;
+
+        search.relax(node_id, cost, |next, next_cost| {
+            let Some(other_cost) = other.tree.cost_to(next) else {
+                return;
+            };
+            let total = next_cost.add(other_cost);
+            if best.is_none_or(|(best_cost, _)| total < best_cost) {
+                best = Some((total, next));
+            }
+        });
+    }
+
+    let (cost, meeting) = best?;
+    let mut path = forward.tree.path_to(meeting)?;
+    let mut rest = backward.tree.path_to(meeting)?;
+    rest.pop();
+    path.extend(rest.into_iter().rev());
+    Some((cost, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::{bidirectional_dijkstra, bidirectional_dijkstra_with};
+    use crate::graph::{dijkstra, dijkstra_to, Graph, NodeVal, QueueStrategy};
+
+    fn path_cost<V: NodeVal>(graph: &Graph<V, u64>, path: &[usize]) -> u64 {
+        path.windows(2)
+            .map(|pair| {
+                graph
+                    .edges(pair[0])
+                    .iter()
+                    .filter(|e| e.dst() == pair[1])
+                    .map(|e| e.cost())
+                    .min()
+                    .expect("consecutive nodes are linked")
+            })
+            .sum()
+    }
+
+    #[test]
+    fn small() {
+        let mut graph = Graph::default();
+        for i in 0..6 {
+            graph.add_node(i);
+        }
+        // the direct edge is the most expensive route
+        graph.add_edge(0, 5, 100);
+        graph.add_edge(0, 1, 10);
+        graph.add_edge(1, 2, 10);
+        graph.add_edge(2, 5, 10);
+        graph.add_edge(0, 3, 1);
+        graph.add_edge(3, 4, 1);
+        graph.add_edge(4, 2, 1);
+        let reverse = graph.reversed();
+
+        assert_eq!(
+            bidirectional_dijkstra(&graph, &reverse, 0, 5),
+            Some((13, vec![0, 3, 4, 2, 5]))
+        );
+        assert_eq!(
+            bidirectional_dijkstra(&graph, &reverse, 3, 3),
+            Some((0, vec![3]))
+        );
+        assert_eq!(bidirectional_dijkstra(&graph, &reverse, 5, 0), None);
+    }
+
+    #[test]
+    fn grid() {
+        let mut graph = Graph::<(usize, usize), u64>::default();
+        let size = 60;
+        let nid = |r: usize, c: usize| r * size + c;
+        for row in 0..size {
+            for col in 0..size {
+                graph.add_node((row, col));
+            }
+        }
+        for row in 0..size {
+            for col in 0..size {
+                let cost = ((row * 7 + col * 13) % 5 + 1) as u64;
+                if row + 1 < size {
+                    graph.add_undirected_edge(nid(row, col), nid(row + 1, col), cost);
+                }
+                if col + 1 < size {
+                    graph.add_undirected_edge(nid(row, col), nid(row, col + 1), cost);
+                }
+            }
+        }
+        let reverse = graph.reversed();
+
+        let from_corner = dijkstra(&graph, 0);
+        for target in [nid(size - 1, size - 1), nid(0, size - 1), nid(30, 17), 1] {
+            let (cost, path) = bidirectional_dijkstra(&graph, &reverse, 0, target).unwrap();
+            assert_eq!(Some(cost), from_corner.cost_to(target));
+            assert_eq!(path_cost(&graph, &path), cost);
+        }
+    }
+
+    #[test]
+    fn random_against_dijkstra() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(2..50);
+            let mut graph = Graph::default();
+            for i in 0..n {
+                graph.add_node(i);
+            }
+            for _ in 0..rng.gen_range(0..n * 4) {
+                graph.add_edge(
+                    rng.gen_range(0..n),
+                    rng.gen_range(0..n),
+                    rng.gen_range(0..20u64),
+                );
+            }
+            let reverse = graph.reversed();
+
+            for _ in 0..20 {
+                let (src, dst) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                let expected = dijkstra_to(&graph, src, dst);
+                let found = bidirectional_dijkstra(&graph, &reverse, src, dst);
+                let found_dk = bidirectional_dijkstra_with(
+                    &graph,
+                    &reverse,
+                    src,
+                    dst,
+                    QueueStrategy::DecreaseKey,
+                );
+                assert_eq!(
+                    found_dk.map(|(cost, _)| cost),
+                    found.as_ref().map(|(cost, _)| *cost)
+                );
+                assert_eq!(
+                    found.as_ref().map(|(cost, _)| *cost),
+                    expected.map(|(cost, _)| cost)
+                );
+                if let Some((cost, path)) = found {
+                    assert_eq!(path.first(), Some(&src));
+                    assert_eq!(path.last(), Some(&dst));
+                    assert_eq!(path_cost(&graph, &path), cost);
+                }
+            }
+        }
+    }
+}
@@ -1,105 +1,186 @@
-use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::collections::HashSet;
 
-pub trait NodeVal: Ord + Copy + Default {}
-impl<T: Ord + Copy + Default> NodeVal for T {}
+use crate::{
+    data_structure::{DaryHeap, DecreaseKey, PriorityQueue},
+    graph::{
+        frontier::{Frontier, HandleQueue, NodeQueue},
+        Graph, NodeId, NodeVal, QueueStrategy, ShortestPathTree, Weight,
+    },
+};
 
-pub type NodeId = usize;
+pub fn dijkstra<V: NodeVal, W: Weight>(graph: &Graph<V, W>, src: NodeId) -> ShortestPathTree<W> {
+    dijkstra_with(graph, src, QueueStrategy::default())
+}
 
-#[derive(Default)]
-pub struct Graph<V: NodeVal> {
-    pub nodes: Vec<V>,
-    pub edges: HashMap<NodeId, Vec<Edge>>,
+/// Dijkstra with a choice of priority queue
+pub fn dijkstra_with<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    src: NodeId,
+    strategy: QueueStrategy,
+) -> ShortestPathTree<W> {
+    search(graph, &[src], Frontier::new(strategy), |_| false).0
 }
 
-#[derive(Debug)]
-pub struct Edge {
-    #[allow(dead_code)]
+/// Dijkstra backed by a d-ary heap with `D` children per node
+///
+/// On dense graphs, nodes are pushed much more often than they are popped,
+/// a wider heap makes these pushes cheaper.
+pub fn dijkstra_dary<V: NodeVal, W: Weight, const D: usize>(
+    graph: &Graph<V, W>,
     src: NodeId,
-    dst: NodeId,
-    cost: usize,
+) -> ShortestPathTree<W> {
+    dijkstra_with_queue::<_, _, DaryHeap<_, D>>(graph, src)
 }
 
-#[derive(Debug, Eq, PartialEq)]
-pub struct QueueEntry {
-    cost: usize,
-    node_id: NodeId,
+/// Dijkstra backed by any [`PriorityQueue`], used with lazy deletion
+///
+/// e.g. `dijkstra_with_queue::<_, _, BinaryHeap<_>>(&graph, 0)`
+pub fn dijkstra_with_queue<V: NodeVal, W: Weight, Q: PriorityQueue<(W, NodeId)> + Default>(
+    graph: &Graph<V, W>,
+    src: NodeId,
+) -> ShortestPathTree<W> {
+    search(graph, &[src], Q::default(), |_| false).0
 }
 
-impl PartialOrd for QueueEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+/// Dijkstra backed by a heap with handles, every node is queued once and lowered with decrease_key
+///
+/// e.g. `dijkstra_with_handles::<_, _, PairingHeap<_>>(&graph, 0)`
+pub fn dijkstra_with_handles<V: NodeVal, W: Weight, Q: DecreaseKey<(W, NodeId)> + Default>(
+    graph: &Graph<V, W>,
+    src: NodeId,
+) -> ShortestPathTree<W> {
+    search(
+        graph,
+        &[src],
+        HandleQueue::<Q, Q::Handle>::default(),
+        |_| false,
+    )
+    .0
 }
 
-impl Ord for QueueEntry {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // NOTE: we reverse the order to get smaller cost at the top
-        other.cost.cmp(&self.cost)
-    }
+/// Shortest path from `src` to `target`, the search stops as soon as `target` is settled
+pub fn dijkstra_to<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    src: NodeId,
+    target: NodeId,
+) -> Option<(W, Vec<NodeId>)> {
+    dijkstra_to_any(graph, src, &[target])
 }
 
-impl<N: NodeVal> Graph<N> {
-    pub fn edges(&self, node_id: NodeId) -> &[Edge] {
-        if let Some(e) = self.edges.get(&node_id) {
-            e
-        } else {
-            &[]
+/// Shortest path from `src` to the nearest of `targets`
+pub fn dijkstra_to_any<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    src: NodeId,
+    targets: &[NodeId],
+) -> Option<(W, Vec<NodeId>)> {
+    let targets = targets.iter().copied().collect::<HashSet<_>>();
+    let queue = Frontier::new(QueueStrategy::default());
+    let (prevs, found) = search(graph, &[src], queue, |node_id| targets.contains(&node_id));
+    let found = found?;
+    Some((prevs.cost_to(found)?, prevs.path_to(found)?))
+}
+
+/// Shortest paths from the nearest of `sources` to every reachable node
+///
+/// This is the same as running Dijkstra from a virtual node linked to every source with a zero cost edge.
+/// Following the predecessors of a node leads back to its nearest source.
+pub fn dijkstra_from_many<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    sources: &[NodeId],
+) -> ShortestPathTree<W> {
+    search(
+        graph,
+        sources,
+        Frontier::new(QueueStrategy::default()),
+        |_| false,
+    )
+    .0
+}
+
+/// Run Dijkstra from `sources` until `stop` returns true for a settled node, which is returned
+fn search<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    sources: &[NodeId],
+    queue: impl NodeQueue<W>,
+    mut stop: impl FnMut(NodeId) -> bool,
+) -> (ShortestPathTree<W>, Option<NodeId>) {
+    let mut search = Search::new(graph, sources, queue);
+    while let Some((node_id, cost)) = search.settle() {
+        if stop(node_id) {
+            return (search.tree, Some(node_id));
         }
+        search.relax(node_id, cost, |_, _| {});
     }
+    (search.tree, None)
+}
 
-    #[allow(dead_code)]
-    fn add_edge(&mut self, src: NodeId, dst: NodeId, cost: usize) {
-        self.edges
-            .entry(src)
-            .or_default()
-            .push(Edge { src, dst, cost });
-    }
+/// Dijkstra advanced one settled node at a time, so that several searches can be interleaved
+pub(crate) struct Search<'a, V: NodeVal, W: Weight, Q> {
+    graph: &'a Graph<V, W>,
+    queue: Q,
+    pub(crate) tree: ShortestPathTree<W>,
 }
 
-pub fn dijkstra<V: NodeVal>(
-    graph: &Graph<V>,
-    src: NodeId,
-) -> BTreeMap<NodeId, (Option<NodeId>, usize)> {
-    let mut prevs = BTreeMap::new();
-    let mut queue = BinaryHeap::new();
-
-    // NOTE: the order of the tuple matters
-    queue.push(QueueEntry {
-        cost: 0,
-        node_id: src,
-    });
-    prevs.insert(src, (None, 0));
-
-    while let Some(e) = queue.pop() {
-        for edge in graph.edges(e.node_id) {
-            if edge.dst == src {
+impl<'a, V: NodeVal, W: Weight, Q: NodeQueue<W>> Search<'a, V, W, Q> {
+    pub(crate) fn new(graph: &'a Graph<V, W>, sources: &[NodeId], mut queue: Q) -> Self {
+        let mut tree = ShortestPathTree::new();
+        for &src in sources {
+            // a repeated source would be settled and relaxed once more
+            if tree.cost_to(src).is_some() {
+                continue;
+            }
+            queue.push(src, W::zero());
+            tree.insert(src, None, W::zero());
+        }
+        Self { graph, queue, tree }
+    }
+
+    /// Pop the next node whose cost is final, `None` once every reachable node is settled
+    pub(crate) fn settle(&mut self) -> Option<(NodeId, W)> {
+        while let Some((node_id, cost)) = self.queue.pop() {
+            // NOTE: with lazy deletion, Rust's BinaryHeap does not support decrease_key
+            // so a node is pushed again every time its cost improves.
+            // The outdated entries are skipped since the node was already settled with a smaller cost
+            if self.tree.cost_to(node_id).is_some_and(|c| cost > c) {
                 continue;
             }
-            let new_cost = e.cost + edge.cost;
-
-            // NOTE: the ideal solution would be to update the cost of the existing nodes in the priority queue
-            // using decrease_key. However, Rust's BinaryHeap does not support this operation.
-            // We accept the duplication of nodes in the priority queue as a tradeoff
-            if prevs
-                .get(&edge.dst)
-                .is_none_or(|(_, prev_cost)| new_cost < *prev_cost)
+            // the first time a node is popped, its cost is final
+            return Some((node_id, cost));
+        }
+        None
+    }
+
+    /// Relax the edges leaving a settled node, `improved` is called with every node whose cost decreased
+    pub(crate) fn relax(&mut self, node_id: NodeId, cost: W, mut improved: impl FnMut(NodeId, W)) {
+        for edge in self.graph.edges(node_id) {
+            // the sources start at zero, the strict comparison below never replaces them
+            let new_cost = cost.add(edge.cost());
+
+            if self
+                .tree
+                .cost_to(edge.dst())
+                .is_none_or(|prev_cost| new_cost < prev_cost)
             {
-                prevs.insert(edge.dst, (Some(e.node_id), new_cost));
-                queue.push(QueueEntry {
-                    cost: new_cost,
-                    node_id: edge.dst,
-                });
+                self.tree.insert(edge.dst(), Some(node_id), new_cost);
+                self.queue.push(edge.dst(), new_cost);
+                improved(edge.dst(), new_cost);
             }
         }
     }
-    prevs
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
-    use super::{dijkstra, Graph};
+    use super::{
+        dijkstra, dijkstra_dary, dijkstra_from_many, dijkstra_to, dijkstra_to_any, dijkstra_with,
+        dijkstra_with_handles, dijkstra_with_queue, Graph, Search,
+    };
+    use crate::{
+        data_structure::{BinaryHeap, FibonacciHeap, PairingHeap},
+        graph::{example_graph, frontier::Frontier, OrdFloat, QueueStrategy},
+    };
 
     #[test]
     fn graph1() {
@@ -120,25 +201,25 @@ mod tests {
         dists_a.insert(2, (Some(0), 12));
         dists_a.insert(3, (Some(2), 44));
         dists_a.insert(1, (Some(2), 32));
-        assert_eq!(dijkstra(&graph, 0), dists_a);
+        assert_eq!(dijkstra(&graph, 0).into_map(), dists_a);
 
         let mut dists_b = BTreeMap::new();
         dists_b.insert(1, (None, 0));
         dists_b.insert(0, (Some(1), 10));
         dists_b.insert(2, (Some(0), 22));
         dists_b.insert(3, (Some(2), 54));
-        assert_eq!(dijkstra(&graph, 1), dists_b);
+        assert_eq!(dijkstra(&graph, 1).into_map(), dists_b);
 
         let mut dists_c = BTreeMap::new();
         dists_c.insert(2, (None, 0));
         dists_c.insert(1, (Some(2), 20));
         dists_c.insert(3, (Some(2), 32));
         dists_c.insert(0, (Some(1), 30));
-        assert_eq!(dijkstra(&graph, 2), dists_c);
+        assert_eq!(dijkstra(&graph, 2).into_map(), dists_c);
 
         let mut dists_d = BTreeMap::new();
         dists_d.insert(3, (None, 0));
-        assert_eq!(dijkstra(&graph, 3), dists_d);
+        assert_eq!(dijkstra(&graph, 3).into_map(), dists_d);
 
         let mut dists_e = BTreeMap::new();
         dists_e.insert(4, (None, 0));
@@ -146,24 +227,12 @@ mod tests {
         dists_e.insert(2, (Some(0), 19));
         dists_e.insert(3, (Some(2), 51));
         dists_e.insert(1, (Some(2), 39));
-        assert_eq!(dijkstra(&graph, 4), dists_e);
+        assert_eq!(dijkstra(&graph, 4).into_map(), dists_e);
     }
 
-    // https://imagedelivery.net/CLfkmk9Wzy8_9HRyug4EVA/482027d5-fb4e-4a3c-d710-ec60cbead600/sharpen=1
     #[test]
     fn graph2() {
-        let mut graph = Graph::default();
-        for i in 0..5 {
-            graph.nodes.push(i);
-        }
-
-        graph.add_edge(0, 1, 10);
-        graph.add_edge(0, 2, 3);
-        graph.add_edge(1, 3, 2);
-        graph.add_edge(2, 1, 4);
-        graph.add_edge(2, 3, 8);
-        graph.add_edge(2, 4, 2);
-        graph.add_edge(3, 4, 5);
+        let graph = example_graph();
 
         let mut expected = BTreeMap::new();
         expected.insert(0, (None, 0));
@@ -171,7 +240,30 @@ mod tests {
         expected.insert(2, (Some(0), 3));
         expected.insert(3, (Some(1), 9));
         expected.insert(4, (Some(2), 5));
-        assert_eq!(dijkstra(&graph, 0), expected);
+        assert_eq!(dijkstra(&graph, 0).into_map(), expected);
+        assert_eq!(
+            dijkstra_with(&graph, 0, QueueStrategy::DecreaseKey).into_map(),
+            expected
+        );
+        assert_eq!(dijkstra_dary::<_, _, 2>(&graph, 0).into_map(), expected);
+        assert_eq!(dijkstra_dary::<_, _, 4>(&graph, 0).into_map(), expected);
+        assert_eq!(dijkstra_dary::<_, _, 8>(&graph, 0).into_map(), expected);
+        assert_eq!(
+            dijkstra_with_queue::<_, _, PairingHeap<_>>(&graph, 0).into_map(),
+            expected
+        );
+        assert_eq!(
+            dijkstra_with_queue::<_, _, FibonacciHeap<_>>(&graph, 0).into_map(),
+            expected
+        );
+        assert_eq!(
+            dijkstra_with_handles::<_, _, PairingHeap<_>>(&graph, 0).into_map(),
+            expected
+        );
+        assert_eq!(
+            dijkstra_with_handles::<_, _, FibonacciHeap<_>>(&graph, 0).into_map(),
+            expected
+        );
     }
 
     #[test]
@@ -197,12 +289,155 @@ mod tests {
         }
 
         let res = dijkstra(&graph, 0);
+        let res_dk = dijkstra_with(&graph, 0, QueueStrategy::DecreaseKey);
+        let res_d2 = dijkstra_dary::<_, _, 2>(&graph, 0);
+        let res_d4 = dijkstra_dary::<_, _, 4>(&graph, 0);
+        let res_d8 = dijkstra_dary::<_, _, 8>(&graph, 0);
+        let res_pairing = dijkstra_with_queue::<_, _, PairingHeap<_>>(&graph, 0);
+        let res_fib = dijkstra_with_queue::<_, _, FibonacciHeap<_>>(&graph, 0);
+        let res_binary = dijkstra_with_queue::<_, _, BinaryHeap<_>>(&graph, 0);
+        let res_pairing_dk = dijkstra_with_handles::<_, _, PairingHeap<_>>(&graph, 0);
+        let res_fib_dk = dijkstra_with_handles::<_, _, FibonacciHeap<_>>(&graph, 0);
         for row in 0..rows {
             for col in 0..cols {
                 let expected_cost = row.max(col);
                 let id = nid(row, col);
-                assert_eq!(res[&id].1, expected_cost);
+                assert_eq!(res.cost_to(id), Some(expected_cost));
+                for other in [
+                    &res_dk,
+                    &res_d2,
+                    &res_d4,
+                    &res_d8,
+                    &res_pairing,
+                    &res_fib,
+                    &res_binary,
+                    &res_pairing_dk,
+                    &res_fib_dk,
+                ] {
+                    assert_eq!(other.cost_to(id), Some(expected_cost));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn float_weights() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, OrdFloat(0.5));
+        graph.add_edge(0, 2, OrdFloat(2.0));
+        graph.add_edge(1, 2, OrdFloat(0.25));
+        graph.add_edge(2, 3, OrdFloat(1.125));
+
+        let res = dijkstra(&graph, 0);
+        assert_eq!(res.cost_to(2), Some(OrdFloat(0.75)));
+        assert_eq!(res.path_to(3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(res.cost_to(3), Some(OrdFloat(1.875)));
+    }
+
+    #[test]
+    fn tuple_weights() {
+        // (hops, latency): the fewest hops is preferred, then the lowest latency
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, (1u32, 1u64));
+        graph.add_edge(1, 2, (1, 1));
+        graph.add_edge(2, 3, (1, 1));
+        graph.add_edge(0, 3, (1, 100));
+
+        let res = dijkstra(&graph, 0);
+        assert_eq!(res.path_to(3), Some(vec![0, 3]));
+        assert_eq!(res.cost_to(3), Some((1, 100)));
+        assert_eq!(res.cost_to(2), Some((2, 2)));
+    }
+
+    #[test]
+    fn to_target() {
+        let graph = example_graph();
+
+        let full = dijkstra(&graph, 0);
+        for target in 0..5 {
+            assert_eq!(
+                dijkstra_to(&graph, 0, target),
+                Some((full.cost_to(target).unwrap(), full.path_to(target).unwrap()))
+            );
+        }
+        assert_eq!(dijkstra_to(&graph, 4, 0), None);
+
+        assert_eq!(
+            dijkstra_to_any(&graph, 0, &[3, 4]),
+            Some((5, vec![0, 2, 4]))
+        );
+        assert_eq!(
+            dijkstra_to_any(&graph, 0, &[1, 3]),
+            Some((7, vec![0, 2, 1]))
+        );
+        assert_eq!(dijkstra_to_any(&graph, 3, &[0, 1, 2]), None);
+        assert_eq!(dijkstra_to_any(&graph, 3, &[]), None);
+    }
+
+    #[test]
+    fn from_many() {
+        let mut graph = Graph::new();
+        let rows = 20;
+        let cols = 20;
+        let nid = |r: usize, c: usize| r * cols + c;
+        for row in 0..rows {
+            for col in 0..cols {
+                graph.add_node((row, col));
+                if row + 1 < rows {
+                    graph.add_undirected_edge(nid(row, col), nid(row + 1, col), 1);
+                }
+                if col + 1 < cols {
+                    graph.add_undirected_edge(nid(row, col), nid(row, col + 1), 1);
+                }
             }
         }
+
+        let sources = [nid(0, 0), nid(19, 19), nid(5, 12)];
+        let res = dijkstra_from_many(&graph, &sources);
+        let singles = sources.map(|src| dijkstra(&graph, src));
+        for id in 0..rows * cols {
+            let expected = singles.iter().map(|t| t.cost_to(id).unwrap()).min();
+            assert_eq!(res.cost_to(id), expected);
+
+            // the path leads back to one of the nearest sources
+            let path = res.path_to(id).unwrap();
+            assert!(sources.contains(&path[0]));
+            assert_eq!(path.len() - 1, expected.unwrap());
+        }
+        for src in sources {
+            assert_eq!(res.path_to(src), Some(vec![src]));
+        }
+
+        // a zero cost edge between two sources does not make one the predecessor of the other
+        let mut graph = Graph::new();
+        for i in 0..3 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, 0);
+        graph.add_edge(1, 2, 4);
+        let res = dijkstra_from_many(&graph, &[0, 1]);
+        assert_eq!(res.path_to(1), Some(vec![1]));
+        assert_eq!(res.path_to(2), Some(vec![1, 2]));
+
+        // every node is settled once, even a source given several times
+        let mut search = Search::new(
+            &graph,
+            &[1, 0, 1, 1],
+            Frontier::new(QueueStrategy::default()),
+        );
+        let mut settled = vec![];
+        while let Some((node_id, cost)) = search.settle() {
+            settled.push(node_id);
+            search.relax(node_id, cost, |_, _| {});
+        }
+        settled.sort_unstable();
+        assert_eq!(settled, vec![0, 1, 2]);
+        assert_eq!(dijkstra_from_many(&graph, &[1, 0, 1, 1]), res);
     }
 }
@@ -0,0 +1,92 @@
+use crate::graph::{NodeId, Weight};
+
+/// Shortest distances between every pair of nodes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMatrix<W: Weight> {
+    size: usize,
+    // row-major: the entry (u, v) is at index u * size + v
+    dists: Vec<Option<W>>,
+    // predecessor of v on the shortest path going from u to v
+    prevs: Vec<Option<NodeId>>,
+}
+
+impl<W: Weight> DistanceMatrix<W> {
+    /// Matrix where every node only reaches itself
+    pub(crate) fn new(size: usize) -> Self {
+        let mut dists = vec![None; size * size];
+        for u in 0..size {
+            dists[u * size + u] = Some(W::zero());
+        }
+        Self {
+            size,
+            dists,
+            prevs: vec![None; size * size],
+        }
+    }
+
+    #[inline]
+    fn idx(&self, u: NodeId, v: NodeId) -> usize {
+        assert!(
+            u < self.size && v < self.size,
+            "({u}, {v}) is out of range for {} nodes",
+            self.size
+        );
+        u * self.size + v
+    }
+
+    pub(crate) fn set(&mut self, u: NodeId, v: NodeId, dist: W, prev: Option<NodeId>) {
+        let idx = self.idx(u, v);
+        self.dists[idx] = Some(dist);
+        self.prevs[idx] = prev;
+    }
+
+    pub(crate) fn prev(&self, u: NodeId, v: NodeId) -> Option<NodeId> {
+        self.prevs[self.idx(u, v)]
+    }
+
+    /// Number of nodes in the matrix
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Cost of the shortest path from `u` to `v`, `None` if `v` is unreachable or either node is out of range
+    pub fn dist(&self, u: NodeId, v: NodeId) -> Option<W> {
+        if u >= self.size || v >= self.size {
+            return None;
+        }
+        self.dists[self.idx(u, v)]
+    }
+
+    /// Nodes along the shortest path from `u` to `v`, both included
+    pub fn path(&self, u: NodeId, v: NodeId) -> Option<Vec<NodeId>> {
+        self.dist(u, v)?;
+
+        let mut path = vec![v];
+        let mut cur = v;
+        while cur != u {
+            cur = self.prev(u, cur)?;
+            path.push(cur);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DistanceMatrix;
+
+    #[test]
+    fn out_of_range() {
+        let mut matrix = DistanceMatrix::new(3);
+        matrix.set(0, 2, 5, Some(0));
+        matrix.set(1, 0, 7, Some(1));
+
+        assert_eq!(matrix.dist(0, 2), Some(5));
+        assert_eq!(matrix.dist(0, 3), None);
+        assert_eq!(matrix.dist(3, 0), None);
+        assert_eq!(matrix.path(0, 2), Some(vec![0, 2]));
+        assert_eq!(matrix.path(0, 3), None);
+        assert_eq!(matrix.path(5, 5), None);
+    }
+}
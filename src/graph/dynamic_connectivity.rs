@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use crate::{data_structure::RollbackDisjointSet, graph::NodeId};
+
+/// Operation on an undirected graph whose edges change over time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Query {
+    AddEdge(NodeId, NodeId),
+    /// Remove one occurrence of an edge, ignored if the edge is not present
+    RemoveEdge(NodeId, NodeId),
+    /// Are the two nodes connected at this point in time
+    Connected(NodeId, NodeId),
+}
+
+/// Answer all the `Connected` queries, in order, on a graph of `node_count` nodes without edges at first
+///
+/// The queries are known in advance, so every edge is alive during a known interval of time.
+/// These intervals are spread over a segment tree over time, and a depth-first walk of the tree
+/// unions the edges of a segment on the way down and rolls them back on the way up.
+/// Every edge is added to O(log q) segments, for O(q log q log n) in total.
+pub fn dynamic_connectivity(node_count: usize, queries: &[Query]) -> Vec<bool> {
+    if queries.is_empty() {
+        return Vec::new();
+    }
+
+    // undirected edges are stored with their smallest node first
+    let key = |u: NodeId, v: NodeId| (u.min(v), u.max(v));
+
+    let mut segments = SegmentTree::new(queries.len());
+    // times at which the copies of an edge still present were added
+    let mut added_at: HashMap<(NodeId, NodeId), Vec<usize>> = HashMap::new();
+    for (time, query) in queries.iter().enumerate() {
+        match *query {
+            Query::AddEdge(u, v) => added_at.entry(key(u, v)).or_default().push(time),
+            Query::RemoveEdge(u, v) => {
+                if let Some(start) = added_at.get_mut(&key(u, v)).and_then(Vec::pop) {
+                    segments.insert(start, time, key(u, v));
+                }
+            }
+            Query::Connected(..) => {}
+        }
+    }
+    // the edges never removed are alive until the end
+    for (edge, starts) in added_at {
+        for start in starts {
+            segments.insert(start, queries.len(), edge);
+        }
+    }
+
+    let mut answers = vec![None; queries.len()];
+    let mut set = RollbackDisjointSet::new(node_count);
+    segments.walk(1, 0, queries.len(), &mut set, &mut |time, set| {
+        if let Query::Connected(u, v) = queries[time] {
+            answers[time] = Some(set.same_set(u, v));
+        }
+    });
+    answers.into_iter().flatten().collect()
+}
+
+/// Segment tree over the query times, every segment holds the edges alive during all of it
+struct SegmentTree {
+    edges: Vec<Vec<(NodeId, NodeId)>>,
+    len: usize,
+}
+
+impl SegmentTree {
+    fn new(len: usize) -> Self {
+        Self {
+            edges: vec![Vec::new(); 4 * len],
+            len,
+        }
+    }
+
+    /// Add `edge` to the segments covering the times `start..end`
+    fn insert(&mut self, start: usize, end: usize, edge: (NodeId, NodeId)) {
+        self.insert_in(1, 0, self.len, start, end, edge);
+    }
+
+    fn insert_in(
+        &mut self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        start: usize,
+        end: usize,
+        edge: (NodeId, NodeId),
+    ) {
+        if end <= lo || hi <= start {
+            return;
+        }
+        if start <= lo && hi <= end {
+            self.edges[node].push(edge);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.insert_in(2 * node, lo, mid, start, end, edge);
+        self.insert_in(2 * node + 1, mid, hi, start, end, edge);
+    }
+
+    /// Call `visit` for every time with the edges alive at that time united in `set`
+    fn walk(
+        &self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        set: &mut RollbackDisjointSet,
+        visit: &mut impl FnMut(usize, &RollbackDisjointSet),
+    ) {
+        let snapshot = set.snapshot();
+        for &(u, v) in &self.edges[node] {
+            set.union(u, v);
+        }
+        if hi - lo == 1 {
+            visit(lo, set);
+        } else {
+            let mid = (lo + hi) / 2;
+            self.walk(2 * node, lo, mid, set, visit);
+            self.walk(2 * node + 1, mid, hi, set, visit);
+        }
+        set.rollback(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::{dynamic_connectivity, Query};
+    use crate::data_structure::DisjointSet;
+
+    #[test]
+    fn test_simple() {
+        use Query::*;
+
+        let queries = [
+            Connected(0, 1),
+            AddEdge(0, 1),
+            AddEdge(1, 2),
+            Connected(0, 2),
+            // a parallel edge keeps the nodes connected when one copy is removed
+            AddEdge(2, 1),
+            RemoveEdge(1, 2),
+            Connected(0, 2),
+            RemoveEdge(2, 1),
+            Connected(0, 2),
+            Connected(0, 1),
+            // removing a missing edge does nothing
+            RemoveEdge(0, 3),
+            AddEdge(3, 2),
+            AddEdge(3, 0),
+            Connected(2, 1),
+            Connected(3, 3),
+        ];
+        assert_eq!(
+            dynamic_connectivity(4, &queries),
+            vec![false, true, true, false, true, true, true]
+        );
+        assert!(dynamic_connectivity(4, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_random_against_rebuild() {
+        let mut rng = rand::thread_rng();
+        let n = 12;
+        let mut queries = Vec::new();
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for _ in 0..400 {
+            let query = match rng.gen_range(0..3) {
+                0 => {
+                    let edge = (rng.gen_range(0..n), rng.gen_range(0..n));
+                    edges.push(edge);
+                    Query::AddEdge(edge.0, edge.1)
+                }
+                1 if !edges.is_empty() => {
+                    let (u, v) = edges.swap_remove(rng.gen_range(0..edges.len()));
+                    Query::RemoveEdge(v, u)
+                }
+                _ => Query::Connected(rng.gen_range(0..n), rng.gen_range(0..n)),
+            };
+            queries.push(query);
+        }
+
+        // rebuild a disjoint set from the alive edges at every query
+        let mut expected = Vec::new();
+        let mut alive: Vec<(usize, usize)> = Vec::new();
+        for query in &queries {
+            match *query {
+                Query::AddEdge(u, v) => alive.push((u.min(v), u.max(v))),
+                Query::RemoveEdge(u, v) => {
+                    let pos = alive.iter().position(|&e| e == (u.min(v), u.max(v)));
+                    alive.swap_remove(pos.unwrap());
+                }
+                Query::Connected(u, v) => {
+                    let mut set = DisjointSet::new(n);
+                    for &(a, b) in &alive {
+                        set.union(a, b);
+                    }
+                    expected.push(set.same_set(u, v));
+                }
+            }
+        }
+        assert_eq!(dynamic_connectivity(n, &queries), expected);
+    }
+}
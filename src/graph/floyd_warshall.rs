@@ -0,0 +1,138 @@
+use crate::graph::{bellman_ford, DistanceMatrix, Graph, NegativeCycle, NodeVal, Weight};
+
+/// Floyd-Warshall all-pairs shortest paths
+///
+/// Runs in O(V^3) regardless of the number of edges, so it is best suited for dense graphs.
+/// Negative edge weights are supported, as long as there is no negative cycle.
+pub fn floyd_warshall<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+) -> Result<DistanceMatrix<W>, NegativeCycle> {
+    let n = graph.nodes.len();
+    let mut matrix = DistanceMatrix::new(n);
+
+    for u in 0..n {
+        for edge in graph.edges(u) {
+            // keep the cheapest of parallel edges
+            if matrix
+                .dist(u, edge.dst())
+                .is_none_or(|cur| edge.cost() < cur)
+            {
+                matrix.set(u, edge.dst(), edge.cost(), Some(u));
+            }
+        }
+    }
+
+    // allow paths going through the nodes 0..=k
+    for k in 0..n {
+        for i in 0..n {
+            let Some(dist_ik) = matrix.dist(i, k) else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(dist_kj) = matrix.dist(k, j) else {
+                    continue;
+                };
+                let new_dist = dist_ik.add(dist_kj);
+                if matrix.dist(i, j).is_none_or(|cur| new_dist < cur) {
+                    // the last hop of i -> j is now the last hop of k -> j
+                    let prev = matrix.prev(k, j);
+                    matrix.set(i, j, new_dist, prev);
+                }
+            }
+        }
+
+        // a node that can reach itself with a negative cost is on a negative cycle.
+        // Stop at the first round where one appears, the costs would keep decreasing
+        // with every round and eventually overflow
+        if let Some(node_id) = (0..n).find(|&u| matrix.dist(u, u).is_some_and(|d| d < W::zero())) {
+            let cycle = bellman_ford(graph, node_id).expect_err("node is on a negative cycle");
+            return Err(cycle);
+        }
+    }
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::floyd_warshall;
+    use crate::graph::{dijkstra, example_graph, Graph};
+
+    #[test]
+    fn same_as_dijkstra() {
+        let graph = example_graph();
+
+        let matrix = floyd_warshall(&graph).unwrap();
+        assert_eq!(matrix.size(), 5);
+        for u in 0..5 {
+            let dists = dijkstra(&graph, u);
+            for v in 0..5 {
+                assert_eq!(matrix.dist(u, v), dists.cost_to(v));
+            }
+        }
+
+        assert_eq!(matrix.path(0, 3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(matrix.path(0, 4), Some(vec![0, 2, 4]));
+        assert_eq!(matrix.path(2, 2), Some(vec![2]));
+        assert_eq!(matrix.path(4, 0), None);
+    }
+
+    #[test]
+    fn negative_weights() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 2, -2);
+        graph.add_edge(1, 0, 4);
+        graph.add_edge(1, 2, 3);
+        graph.add_edge(2, 3, 2);
+        graph.add_edge(3, 1, -1);
+
+        let matrix = floyd_warshall(&graph).unwrap();
+        assert_eq!(matrix.dist(0, 1), Some(-1));
+        assert_eq!(matrix.path(0, 1), Some(vec![0, 2, 3, 1]));
+        assert_eq!(matrix.dist(1, 3), Some(4));
+        assert_eq!(matrix.path(1, 3), Some(vec![1, 0, 2, 3]));
+        assert_eq!(matrix.dist(3, 0), Some(3));
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, -1);
+        graph.add_edge(2, 3, -1);
+        graph.add_edge(3, 1, 1);
+
+        let err = floyd_warshall(&graph).unwrap_err();
+        let mut cycle = err.cycle;
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn dense_negative_cycles() {
+        // every pair of nodes is linked by a negative edge, which would overflow
+        // if the relaxation went on after the first negative cycle
+        let mut graph = Graph::<usize, i64>::new();
+        let mut graph_i32 = Graph::<usize, i32>::new();
+        for i in 0..60 {
+            graph.add_node(i);
+            graph_i32.add_node(i);
+        }
+        for u in 0..60 {
+            for v in 0..60 {
+                if u != v {
+                    graph.add_edge(u, v, -1);
+                    graph_i32.add_edge(u, v, -1000);
+                }
+            }
+        }
+
+        assert!(!floyd_warshall(&graph).unwrap_err().cycle.is_empty());
+        assert!(!floyd_warshall(&graph_i32).unwrap_err().cycle.is_empty());
+    }
+}
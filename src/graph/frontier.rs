@@ -0,0 +1,144 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::{
+    data_structure::{binary_heap::HeapVal, DecreaseKey, IndexedBinaryHeap, PriorityQueue},
+    graph::NodeId,
+};
+
+/// Priority queue used by the best-first searches (Dijkstra, A*, Prim)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QueueStrategy {
+    /// Push a new entry every time a node improves, and skip the outdated ones when popped.
+    /// The queue may grow up to the number of edges.
+    #[default]
+    LazyDeletion,
+    /// Keep a single entry per node and lower its priority in place with decrease_key.
+    /// The queue never holds more than the number of nodes.
+    DecreaseKey,
+}
+
+/// Queue of nodes ordered by the smallest priority
+pub(crate) trait NodeQueue<P> {
+    /// Queue `node_id`, or lower its priority if it is already queued
+    fn push(&mut self, node_id: NodeId, priority: P);
+
+    fn pop(&mut self) -> Option<(NodeId, P)>;
+}
+
+/// Queue selected at runtime with a [`QueueStrategy`]
+pub(crate) enum Frontier<P: HeapVal> {
+    Lazy(BinaryHeap<Reverse<(P, NodeId)>>),
+    Indexed(IndexedBinaryHeap<P>),
+}
+
+impl<P: HeapVal> Frontier<P> {
+    pub(crate) fn new(strategy: QueueStrategy) -> Self {
+        match strategy {
+            QueueStrategy::LazyDeletion => Self::Lazy(BinaryHeap::new()),
+            QueueStrategy::DecreaseKey => Self::Indexed(IndexedBinaryHeap::new()),
+        }
+    }
+}
+
+impl<P: HeapVal> NodeQueue<P> for Frontier<P> {
+    fn push(&mut self, node_id: NodeId, priority: P) {
+        match self {
+            Self::Lazy(queue) => queue.push(Reverse((priority, node_id))),
+            Self::Indexed(queue) => {
+                if queue.contains(node_id) {
+                    queue.decrease_key(node_id, priority);
+                } else {
+                    queue.push(node_id, priority);
+                }
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(NodeId, P)> {
+        match self {
+            Self::Lazy(queue) => queue
+                .pop()
+                .map(|Reverse((priority, node_id))| (node_id, priority)),
+            Self::Indexed(queue) => queue.pop(),
+        }
+    }
+}
+
+/// Heap with a handle for every queued node, so that an improved node is lowered in place
+pub(crate) struct HandleQueue<Q, H> {
+    heap: Q,
+    handles: HashMap<NodeId, H>,
+}
+
+impl<Q: Default, H> Default for HandleQueue<Q, H> {
+    fn default() -> Self {
+        Self {
+            heap: Q::default(),
+            handles: HashMap::new(),
+        }
+    }
+}
+
+impl<P, Q: DecreaseKey<(P, NodeId)>> NodeQueue<P> for HandleQueue<Q, Q::Handle> {
+    fn push(&mut self, node_id: NodeId, priority: P) {
+        match self.handles.get(&node_id) {
+            // a larger priority is refused and the node keeps its place
+            Some(handle) => {
+                self.heap.decrease_key(handle, (priority, node_id));
+            }
+            None => {
+                let handle = self.heap.push((priority, node_id));
+                self.handles.insert(node_id, handle);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(NodeId, P)> {
+        let (priority, node_id) = self.heap.pop()?;
+        self.handles.remove(&node_id);
+        Some((node_id, priority))
+    }
+}
+
+// without handles, the generic heaps are used with lazy deletion
+impl<P, Q: PriorityQueue<(P, NodeId)>> NodeQueue<P> for Q {
+    fn push(&mut self, node_id: NodeId, priority: P) {
+        PriorityQueue::push(self, (priority, node_id));
+    }
+
+    fn pop(&mut self) -> Option<(NodeId, P)> {
+        PriorityQueue::pop(self).map(|(priority, node_id)| (node_id, priority))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HandleQueue, NodeQueue};
+    use crate::data_structure::{FibonacciHeap, PairingHeap};
+
+    #[test]
+    fn handle_queue_keeps_one_entry_per_node() {
+        let mut queue = HandleQueue::<PairingHeap<_>, _>::default();
+        queue.push(0, 10);
+        queue.push(1, 5);
+        queue.push(0, 3);
+        // a larger priority does not replace the queued one
+        queue.push(1, 8);
+
+        assert_eq!(queue.pop(), Some((0, 3)));
+        assert_eq!(queue.pop(), Some((1, 5)));
+        assert_eq!(queue.pop(), None);
+
+        // a popped node can be queued again
+        let mut queue = HandleQueue::<FibonacciHeap<_>, _>::default();
+        queue.push(2, 1);
+        assert_eq!(queue.pop(), Some((2, 1)));
+        queue.push(2, 4);
+        queue.push(2, 2);
+        assert_eq!(queue.pop(), Some((2, 2)));
+        assert_eq!(queue.pop(), None);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::graph::{
+    bellman_ford::potentials, dijkstra, DistanceMatrix, Graph, NegativeCycle, NodeVal, SubWeight,
+};
+
+/// Johnson's all-pairs shortest paths
+///
+/// Negative edges are removed by reweighting them with potentials computed by Bellman-Ford,
+/// then Dijkstra is run from every node.
+/// Runs in O(V * E * log(V)), which beats Floyd-Warshall on sparse graphs.
+pub fn johnson<V: NodeVal, W: SubWeight>(
+    graph: &Graph<V, W>,
+) -> Result<DistanceMatrix<W>, NegativeCycle> {
+    let n = graph.nodes.len();
+    let h = potentials(graph)?;
+
+    // the potentials satisfy h(v) <= h(u) + cost(u, v), so every new cost is non-negative
+    // a path from u to v is shifted by h(u) - h(v) no matter which nodes it goes through
+    let mut reweighted = Graph::new();
+    reweighted.nodes = graph.nodes.clone();
+    for u in 0..n {
+        for edge in graph.edges(u) {
+            let cost = edge.cost().add(h[u]).sub(h[edge.dst()]);
+            reweighted.add_edge(u, edge.dst(), cost);
+        }
+    }
+
+    let mut matrix = DistanceMatrix::new(n);
+    for u in 0..n {
+        for (v, (prev, dist)) in dijkstra(&reweighted, u).into_map() {
+            matrix.set(u, v, dist.add(h[v]).sub(h[u]), prev);
+        }
+    }
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::johnson;
+    use crate::graph::{bellman_ford, floyd_warshall, Graph, OrdFloat};
+
+    #[test]
+    fn negative_weights() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 2, -2);
+        graph.add_edge(1, 0, 4);
+        graph.add_edge(1, 2, 3);
+        graph.add_edge(2, 3, 2);
+        graph.add_edge(3, 1, -1);
+
+        let matrix = johnson(&graph).unwrap();
+        assert_eq!(matrix, floyd_warshall(&graph).unwrap());
+        assert_eq!(matrix.dist(0, 1), Some(-1));
+        assert_eq!(matrix.path(0, 1), Some(vec![0, 2, 3, 1]));
+        assert_eq!(matrix.path(1, 3), Some(vec![1, 0, 2, 3]));
+    }
+
+    #[test]
+    fn float_weights() {
+        let mut graph = Graph::new();
+        for i in 0..3 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, OrdFloat(1.5));
+        graph.add_edge(1, 2, OrdFloat(-0.5));
+        graph.add_edge(0, 2, OrdFloat(1.25));
+
+        let matrix = johnson(&graph).unwrap();
+        assert_eq!(matrix.dist(0, 2), Some(OrdFloat(1.0)));
+        assert_eq!(matrix.path(0, 2), Some(vec![0, 1, 2]));
+        assert_eq!(matrix.dist(2, 0), None);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(1, 2, -1);
+        graph.add_edge(2, 3, -1);
+        graph.add_edge(3, 1, 1);
+
+        let mut cycle = johnson(&graph).unwrap_err().cycle;
+        cycle.sort();
+        assert_eq!(cycle, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn random_graphs() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..20 {
+            let n = 30;
+            let mut graph = Graph::new();
+            for i in 0..n {
+                graph.add_node(i);
+            }
+            // edges only go forward so there is no cycle, negative or not
+            for _ in 0..150 {
+                let u = rng.gen_range(0..n - 1);
+                let v = rng.gen_range(u + 1..n);
+                graph.add_edge(u, v, rng.gen_range(-20i64..50));
+            }
+
+            let fw = floyd_warshall(&graph).unwrap();
+            let jo = johnson(&graph).unwrap();
+            for u in 0..n {
+                let bf = bellman_ford(&graph, u).unwrap();
+                for v in 0..n {
+                    let expected = bf.cost_to(v);
+                    assert_eq!(fw.dist(u, v), expected);
+                    assert_eq!(jo.dist(u, v), expected);
+
+                    // the reconstructed path must have the advertised cost
+                    if let Some(path) = jo.path(u, v) {
+                        let cost: i64 = path
+                            .windows(2)
+                            .map(|w| {
+                                graph
+                                    .edges(w[0])
+                                    .iter()
+                                    .filter(|e| e.dst() == w[1])
+                                    .map(|e| e.cost())
+                                    .min()
+                                    .unwrap()
+                            })
+                            .sum();
+                        assert_eq!(Some(cost), expected);
+                    }
+                }
+            }
+        }
+    }
+}
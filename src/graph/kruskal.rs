@@ -1,74 +1,40 @@
-use std::collections::HashMap;
-
 use crate::data_structure::DisjointSet;
+use crate::graph::{Edge, Graph, MstResult, NodeVal, Weight};
 
-pub trait NodeVal: Ord + Copy + Default {}
-impl<T: Ord + Copy + Default> NodeVal for T {}
-
-pub type NodeId = usize;
-
-#[derive(Default)]
-pub struct Graph<V: NodeVal> {
-    pub nodes: Vec<V>,
-    pub edges: HashMap<NodeId, Vec<Edge>>,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Edge {
-    #[allow(dead_code)]
-    src: NodeId,
-    dst: NodeId,
-    cost: usize,
-}
-
-impl<N: NodeVal> Graph<N> {
-    pub fn edges(&self, node_id: NodeId) -> &[Edge] {
-        if let Some(e) = self.edges.get(&node_id) {
-            e
-        } else {
-            &[]
-        }
-    }
-
-    #[allow(dead_code)]
-    fn add_edge(&mut self, src: NodeId, dst: NodeId, cost: usize) {
-        self.edges
-            .entry(src)
-            .or_default()
-            .push(Edge { src, dst, cost });
-    }
-}
-
-pub fn kruskal<V: NodeVal>(graph: &Graph<V>) -> Vec<Edge> {
+/// Kruskal minimum spanning forest
+///
+/// Edges are considered undirected, so both directions of an undirected edge are only taken once.
+pub fn kruskal<V: NodeVal, W: Weight>(graph: &Graph<V, W>) -> MstResult<W> {
     let mut disjoint_set = DisjointSet::new(graph.nodes.len());
 
     // sort edges
-    let mut all_edges: Vec<Edge> = graph.edges.values().flatten().cloned().collect::<Vec<_>>();
-    all_edges.sort_by_key(|e| e.cost);
+    let mut all_edges: Vec<Edge<W>> = graph.all_edges().copied().collect();
+    all_edges.sort_by_key(|e| e.cost());
 
     let mut mst = Vec::new();
     for edge in all_edges {
-        // if the edge does not form a cycle
-        if disjoint_set.find(edge.src) != disjoint_set.find(edge.dst) {
-            // add it to the forest
-            disjoint_set.union(edge.src, edge.dst);
+        // if the edge does not form a cycle, add it to the forest
+        if disjoint_set.union(edge.src(), edge.dst()) {
             mst.push(edge);
         }
     }
-    mst
+    MstResult::new(mst, disjoint_set.sets())
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
-    use crate::graph::kruskal::{kruskal, Edge, Graph};
+    use super::{kruskal, Edge, Graph};
+    use crate::graph::OrdFloat;
 
     #[test]
     fn test_empty_graph() {
         let graph: Graph<i32> = Graph::default();
         let mst = kruskal(&graph);
-        assert!(mst.is_empty());
+        assert!(mst.edges.is_empty());
+        assert!(mst.components.is_empty());
+        assert!(mst.is_connected);
     }
 
     #[test]
@@ -85,8 +51,8 @@ mod tests {
         graph.add_edge(1, 3, 5);
         let mst = kruskal(&graph);
 
-        assert_eq!(mst.len(), 4);
-        assert_eq!(mst.iter().map(|e| e.cost).sum::<usize>(), 10);
+        assert_eq!(mst.edges.len(), 4);
+        assert_eq!(mst.total_cost, 10);
     }
 
     #[test]
@@ -102,8 +68,8 @@ mod tests {
         graph.add_edge(0, 2, 10);
 
         let mst = kruskal(&graph);
-        assert_eq!(mst.len(), 3);
-        assert_eq!(mst.iter().map(|e| e.cost).sum::<usize>(), 6);
+        assert_eq!(mst.edges.len(), 3);
+        assert_eq!(mst.total_cost, 6);
     }
 
     #[test]
@@ -126,8 +92,9 @@ mod tests {
         graph.add_edge(3, 4, 2);
 
         let mst = kruskal(&graph);
-        assert_eq!(mst.len(), 6);
-        assert_eq!(mst.iter().map(|e| e.cost).sum::<usize>(), 16);
+        assert_eq!(mst.edges.len(), 6);
+        assert_eq!(mst.total_cost, 16);
+        assert!(mst.is_connected);
 
         let res = [
             (1, 3, 1),
@@ -138,12 +105,54 @@ mod tests {
             (2, 6, 6),
         ]
         .iter()
-        .map(|(src, dst, cost)| Edge {
-            src: *src,
-            dst: *dst,
-            cost: *cost,
-        })
+        .map(|&(src, dst, cost)| Edge::new(src, dst, cost))
         .collect::<BTreeSet<_>>();
-        assert_eq!(BTreeSet::from_iter(mst.into_iter()), res);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+    }
+
+    #[test]
+    fn float_weights() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, OrdFloat(0.5));
+        graph.add_edge(1, 2, OrdFloat(1.5));
+        graph.add_edge(0, 2, OrdFloat(0.75));
+        graph.add_edge(2, 3, OrdFloat(-2.0));
+
+        let mst = kruskal(&graph);
+        assert_eq!(mst.total_cost, OrdFloat(-0.75));
+        assert_eq!(
+            mst.edges,
+            vec![
+                Edge::new(2, 3, OrdFloat(-2.0)),
+                Edge::new(0, 1, OrdFloat(0.5)),
+                Edge::new(0, 2, OrdFloat(0.75)),
+            ]
+        );
+    }
+
+    #[test]
+    fn forest() {
+        let mut graph = Graph::new();
+        for i in 0..7 {
+            graph.add_node(i);
+        }
+        graph.add_undirected_edge(0, 3, 4);
+        graph.add_undirected_edge(3, 5, 1);
+        graph.add_undirected_edge(0, 5, 2);
+        graph.add_undirected_edge(1, 4, 7);
+        // duplicate undirected edge
+        graph.add_undirected_edge(4, 1, 3);
+
+        let mst = kruskal(&graph);
+        assert_eq!(mst.edges.len(), 3);
+        assert_eq!(mst.total_cost, 6);
+        assert_eq!(
+            mst.components,
+            vec![vec![0, 3, 5], vec![1, 4], vec![2], vec![6]]
+        );
+        assert!(!mst.is_connected);
     }
 }
@@ -1,9 +1,53 @@
 mod astar;
+mod bellman_ford;
+mod bidirectional_dijkstra;
 mod dijkstra;
+mod distance_matrix;
+mod dynamic_connectivity;
+mod floyd_warshall;
+mod frontier;
+mod johnson;
 mod kruskal;
+mod mst_result;
 mod prim;
+mod shortest_path_tree;
+mod weight;
+mod weighted_graph;
 
-pub use astar::astar;
-pub use dijkstra::dijkstra;
+pub use astar::{astar, astar_with};
+pub use bellman_ford::{bellman_ford, NegativeCycle};
+pub use bidirectional_dijkstra::{bidirectional_dijkstra, bidirectional_dijkstra_with};
+pub use dijkstra::{
+    dijkstra, dijkstra_dary, dijkstra_from_many, dijkstra_to, dijkstra_to_any, dijkstra_with,
+    dijkstra_with_handles, dijkstra_with_queue,
+};
+pub use distance_matrix::DistanceMatrix;
+pub use dynamic_connectivity::{dynamic_connectivity, Query};
+pub use floyd_warshall::floyd_warshall;
+pub use frontier::QueueStrategy;
+pub use johnson::johnson;
 pub use kruskal::kruskal;
-pub use prim::prim;
+pub use mst_result::MstResult;
+pub use prim::{prim, prim_with, prim_with_handles, prim_with_queue};
+pub use shortest_path_tree::ShortestPathTree;
+pub use weight::{OrdFloat, SubWeight, Weight};
+pub use weighted_graph::{Edge, Graph, NodeId, NodeVal};
+
+/// Small directed graph shared by the shortest path tests
+///
+/// https://imagedelivery.net/CLfkmk9Wzy8_9HRyug4EVA/482027d5-fb4e-4a3c-d710-ec60cbead600/sharpen=1
+#[cfg(test)]
+pub(crate) fn example_graph() -> Graph<usize> {
+    let mut graph = Graph::new();
+    for i in 0..5 {
+        graph.add_node(i);
+    }
+    graph.add_edge(0, 1, 10);
+    graph.add_edge(0, 2, 3);
+    graph.add_edge(1, 3, 2);
+    graph.add_edge(2, 1, 4);
+    graph.add_edge(2, 3, 8);
+    graph.add_edge(2, 4, 2);
+    graph.add_edge(3, 4, 5);
+    graph
+}
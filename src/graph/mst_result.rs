@@ -0,0 +1,24 @@
+use crate::graph::{Edge, NodeId, Weight};
+
+/// Minimum spanning forest of a graph, made of one minimum spanning tree per connected component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MstResult<W: Weight> {
+    pub edges: Vec<Edge<W>>,
+    pub total_cost: W,
+    /// Nodes of every tree in increasing order, the trees being ordered by their smallest node
+    pub components: Vec<Vec<NodeId>>,
+    /// Whether the forest is a single tree spanning all the nodes
+    pub is_connected: bool,
+}
+
+impl<W: Weight> MstResult<W> {
+    pub(crate) fn new(edges: Vec<Edge<W>>, components: Vec<Vec<NodeId>>) -> Self {
+        let total_cost = edges.iter().fold(W::zero(), |acc, e| acc.add(e.cost()));
+        Self {
+            edges,
+            total_cost,
+            is_connected: components.len() <= 1,
+            components,
+        }
+    }
+}
@@ -1,84 +1,101 @@
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashMap, HashSet},
+use crate::{
+    data_structure::{DecreaseKey, PriorityQueue},
+    graph::{
+        frontier::{Frontier, HandleQueue, NodeQueue},
+        Edge, Graph, MstResult, NodeId, NodeVal, QueueStrategy, Weight,
+    },
 };
 
-pub trait NodeVal: Ord + Copy + Default {}
-impl<T: Ord + Copy + Default> NodeVal for T {}
-
-pub type NodeId = usize;
-
-#[derive(Default)]
-pub struct Graph<V: NodeVal> {
-    pub nodes: Vec<V>,
-    pub edges: HashMap<NodeId, Vec<Edge>>,
+/// Prim minimum spanning forest
+///
+/// The search restarts from every node left unvisited, so that every connected component gets its tree.
+/// Like [`kruskal`](crate::graph::kruskal), every edge is followed both ways whatever its direction,
+/// and the edges of the forest are oriented away from the node their tree grew from.
+pub fn prim<V: NodeVal, W: Weight>(graph: &Graph<V, W>) -> MstResult<W> {
+    prim_with(graph, QueueStrategy::default())
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Edge {
-    #[allow(dead_code)]
-    src: NodeId,
-    dst: NodeId,
-    cost: usize,
+/// Prim with a choice of priority queue
+///
+/// Nodes outside the tree are queued with the cheapest edge linking them to the tree.
+pub fn prim_with<V: NodeVal, W: Weight>(
+    graph: &Graph<V, W>,
+    strategy: QueueStrategy,
+) -> MstResult<W> {
+    grow(graph, || Frontier::new(strategy))
 }
 
-impl<N: NodeVal> Graph<N> {
-    pub fn edges(&self, node_id: NodeId) -> &[Edge] {
-        if let Some(e) = self.edges.get(&node_id) {
-            e
-        } else {
-            &[]
-        }
-    }
+/// Prim backed by any [`PriorityQueue`], used with lazy deletion
+pub fn prim_with_queue<V, W, Q>(graph: &Graph<V, W>) -> MstResult<W>
+where
+    V: NodeVal,
+    W: Weight,
+    Q: PriorityQueue<((W, Edge<W>), NodeId)> + Default,
+{
+    grow(graph, Q::default)
+}
 
-    #[allow(dead_code)]
-    fn add_edge(&mut self, src: NodeId, dst: NodeId, cost: usize) {
-        self.edges
-            .entry(src)
-            .or_default()
-            .push(Edge { src, dst, cost });
-        self.edges.entry(dst).or_default().push(Edge {
-            src: dst,
-            dst: src,
-            cost,
-        });
-    }
+/// Prim backed by a heap with handles, every node is queued once and lowered with decrease_key
+pub fn prim_with_handles<V, W, Q>(graph: &Graph<V, W>) -> MstResult<W>
+where
+    V: NodeVal,
+    W: Weight,
+    Q: DecreaseKey<((W, Edge<W>), NodeId)> + Default,
+{
+    grow(graph, HandleQueue::<Q, Q::Handle>::default)
 }
 
-pub fn prim<V: NodeVal>(graph: &Graph<V>) -> Vec<Edge> {
+fn grow<V: NodeVal, W: Weight, Q: NodeQueue<(W, Edge<W>)>>(
+    graph: &Graph<V, W>,
+    mut new_queue: impl FnMut() -> Q,
+) -> MstResult<W> {
+    // the edges entering a node link it to the tree as well
+    let reverse = graph.reversed();
     let mut mst = vec![];
-    let mut q = BinaryHeap::new();
-    let mut seen = HashSet::new();
-    // start with an arbitrary node, push all its edges
-    for &e in graph.edges(0) {
-        q.push(Reverse((e.cost, e)));
-    }
-    seen.insert(0);
+    let mut components = vec![];
+    let mut seen = vec![false; graph.nodes.len()];
 
-    while let Some(e) = q.pop() {
-        let (_, edge) = e.0;
-        if !seen.insert(edge.dst) {
+    for start in 0..graph.nodes.len() {
+        if seen[start] {
             continue;
         }
-        mst.push(edge);
-        if mst.len() == graph.nodes.len() {
-            break;
+        // grow a new tree from the first node not spanned yet
+        let mut q = new_queue();
+        let mut component = vec![start];
+        for &e in graph.edges(start).iter().chain(reverse.edges(start)) {
+            q.push(e.dst(), (e.cost(), e));
         }
+        seen[start] = true;
 
-        for &edge in graph.edges(edge.dst) {
-            if !seen.contains(&edge.dst) {
-                q.push(Reverse((edge.cost, edge)));
+        while let Some((node_id, (_, edge))) = q.pop() {
+            if seen[node_id] {
+                continue;
+            }
+            seen[node_id] = true;
+            component.push(node_id);
+            mst.push(edge);
+
+            for &edge in graph.edges(node_id).iter().chain(reverse.edges(node_id)) {
+                if !seen[edge.dst()] {
+                    q.push(edge.dst(), (edge.cost(), edge));
+                }
             }
         }
+        component.sort_unstable();
+        components.push(component);
     }
-    mst
+    MstResult::new(mst, components)
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
-    use crate::graph::prim::{prim, Edge, Graph};
+    use super::{prim, prim_with, prim_with_handles, prim_with_queue, Edge, Graph};
+    use crate::{
+        data_structure::{BinaryHeap, FibonacciHeap, PairingHeap},
+        graph::{kruskal, QueueStrategy},
+    };
 
     #[test]
     fn graph1() {
@@ -86,16 +103,16 @@ mod tests {
             nodes: vec![1, 2, 3, 4, 5],
             edges: Default::default(),
         };
-        graph.add_edge(0, 1, 1);
-        graph.add_edge(1, 2, 2);
-        graph.add_edge(2, 3, 3);
-        graph.add_edge(3, 4, 4);
-        graph.add_edge(0, 4, 10);
-        graph.add_edge(1, 3, 5);
+        graph.add_undirected_edge(0, 1, 1);
+        graph.add_undirected_edge(1, 2, 2);
+        graph.add_undirected_edge(2, 3, 3);
+        graph.add_undirected_edge(3, 4, 4);
+        graph.add_undirected_edge(0, 4, 10);
+        graph.add_undirected_edge(1, 3, 5);
         let mst = prim(&graph);
 
-        assert_eq!(mst.len(), 4);
-        assert_eq!(mst.iter().map(|e| e.cost).sum::<usize>(), 10);
+        assert_eq!(mst.edges.len(), 4);
+        assert_eq!(mst.total_cost, 10);
     }
 
     #[test]
@@ -104,15 +121,15 @@ mod tests {
             nodes: vec![1, 2, 3, 4],
             edges: Default::default(),
         };
-        graph.add_edge(0, 1, 1);
-        graph.add_edge(1, 2, 2);
-        graph.add_edge(2, 3, 3);
-        graph.add_edge(3, 0, 4);
-        graph.add_edge(0, 2, 10);
+        graph.add_undirected_edge(0, 1, 1);
+        graph.add_undirected_edge(1, 2, 2);
+        graph.add_undirected_edge(2, 3, 3);
+        graph.add_undirected_edge(3, 0, 4);
+        graph.add_undirected_edge(0, 2, 10);
 
         let mst = prim(&graph);
-        assert_eq!(mst.len(), 3);
-        assert_eq!(mst.iter().map(|e| e.cost).sum::<usize>(), 6);
+        assert_eq!(mst.edges.len(), 3);
+        assert_eq!(mst.total_cost, 6);
     }
 
     #[test]
@@ -121,22 +138,22 @@ mod tests {
             nodes: vec![0, 1, 2, 3, 4, 5, 6],
             edges: Default::default(),
         };
-        graph.add_edge(0, 1, 2);
-        graph.add_edge(0, 3, 4);
-        graph.add_edge(0, 5, 5);
-        graph.add_edge(1, 3, 1);
-        graph.add_edge(1, 5, 8);
-        graph.add_edge(1, 4, 3);
-        graph.add_edge(1, 2, 7);
-        graph.add_edge(1, 6, 4);
-        graph.add_edge(2, 6, 6);
-        graph.add_edge(2, 4, 10);
-        graph.add_edge(5, 6, 1);
-        graph.add_edge(3, 4, 2);
+        graph.add_undirected_edge(0, 1, 2);
+        graph.add_undirected_edge(0, 3, 4);
+        graph.add_undirected_edge(0, 5, 5);
+        graph.add_undirected_edge(1, 3, 1);
+        graph.add_undirected_edge(1, 5, 8);
+        graph.add_undirected_edge(1, 4, 3);
+        graph.add_undirected_edge(1, 2, 7);
+        graph.add_undirected_edge(1, 6, 4);
+        graph.add_undirected_edge(2, 6, 6);
+        graph.add_undirected_edge(2, 4, 10);
+        graph.add_undirected_edge(5, 6, 1);
+        graph.add_undirected_edge(3, 4, 2);
 
         let mst = prim(&graph);
-        assert_eq!(mst.len(), 6);
-        assert_eq!(mst.iter().map(|e| e.cost).sum::<usize>(), 16);
+        assert_eq!(mst.edges.len(), 6);
+        assert_eq!(mst.total_cost, 16);
 
         let res = [
             (0, 1, 2),
@@ -147,12 +164,84 @@ mod tests {
             (6, 2, 6),
         ]
         .iter()
-        .map(|(src, dst, cost)| Edge {
-            src: *src,
-            dst: *dst,
-            cost: *cost,
-        })
+        .map(|&(src, dst, cost)| Edge::new(src, dst, cost))
         .collect::<BTreeSet<_>>();
-        assert_eq!(BTreeSet::from_iter(mst.into_iter()), res);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+
+        let mst = prim_with(&graph, QueueStrategy::DecreaseKey);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+
+        let mst = prim_with_queue::<_, _, PairingHeap<_>>(&graph);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+        let mst = prim_with_queue::<_, _, FibonacciHeap<_>>(&graph);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+        let mst = prim_with_queue::<_, _, BinaryHeap<_>>(&graph);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+        let mst = prim_with_handles::<_, _, PairingHeap<_>>(&graph);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+        let mst = prim_with_handles::<_, _, FibonacciHeap<_>>(&graph);
+        assert_eq!(BTreeSet::from_iter(mst.edges.into_iter()), res);
+    }
+
+    #[test]
+    fn forest() {
+        let mut graph = Graph::new();
+        for i in 0..7 {
+            graph.add_node(i);
+        }
+        graph.add_undirected_edge(0, 3, 4);
+        graph.add_undirected_edge(3, 5, 1);
+        graph.add_undirected_edge(0, 5, 2);
+        graph.add_undirected_edge(1, 4, 7);
+        // duplicate undirected edge
+        graph.add_undirected_edge(4, 1, 3);
+
+        let mst = prim(&graph);
+        assert_eq!(mst.edges.len(), 3);
+        assert_eq!(mst.total_cost, 6);
+        assert_eq!(
+            mst.components,
+            vec![vec![0, 3, 5], vec![1, 4], vec![2], vec![6]]
+        );
+        assert!(!mst.is_connected);
+
+        // both algorithms agree on the forest
+        let other = kruskal(&graph);
+        assert_eq!(other.total_cost, mst.total_cost);
+        assert_eq!(other.components, mst.components);
+
+        let empty: Graph<i32> = Graph::new();
+        let mst = prim(&empty);
+        assert!(mst.edges.is_empty());
+        assert!(mst.is_connected);
+    }
+
+    #[test]
+    fn directed_edges() {
+        let mut graph = Graph::new();
+        for i in 0..4 {
+            graph.add_node(i);
+        }
+        graph.add_edge(1, 0, 5);
+        graph.add_edge(2, 1, 1);
+        graph.add_edge(0, 2, 7);
+
+        let mst = prim(&graph);
+        let other = kruskal(&graph);
+        assert_eq!(mst.edges.len(), 2);
+        assert_eq!(mst.total_cost, 6);
+        assert_eq!(mst.components, vec![vec![0, 1, 2], vec![3]]);
+        assert_eq!(other.edges.len(), mst.edges.len());
+        assert_eq!(other.total_cost, mst.total_cost);
+        assert_eq!(other.components, mst.components);
+        assert_eq!(other.is_connected, mst.is_connected);
+
+        // grown from node 0, the edges point away from it
+        assert_eq!(
+            BTreeSet::from_iter(mst.edges.into_iter()),
+            BTreeSet::from([Edge::new(0, 1, 5), Edge::new(1, 2, 1)])
+        );
+        let mst = prim_with(&graph, QueueStrategy::DecreaseKey);
+        assert_eq!(mst.total_cost, 6);
     }
 }
@@ -0,0 +1,185 @@
+use std::collections::BTreeMap;
+
+use crate::graph::{Graph, NodeId, NodeVal, Weight};
+
+/// Result of a single source shortest path search
+///
+/// Every reached node is mapped to its predecessor on the shortest path and to the cost of that path.
+/// The source has no predecessor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPathTree<W: Weight> {
+    prevs: BTreeMap<NodeId, (Option<NodeId>, W)>,
+}
+
+impl<W: Weight> Default for ShortestPathTree<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W: Weight> From<BTreeMap<NodeId, (Option<NodeId>, W)>> for ShortestPathTree<W> {
+    fn from(prevs: BTreeMap<NodeId, (Option<NodeId>, W)>) -> Self {
+        Self { prevs }
+    }
+}
+
+impl<W: Weight> ShortestPathTree<W> {
+    pub fn new() -> Self {
+        Self {
+            prevs: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, node_id: NodeId, prev: Option<NodeId>, cost: W) {
+        self.prevs.insert(node_id, (prev, cost));
+    }
+
+    pub fn len(&self) -> usize {
+        self.prevs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prevs.is_empty()
+    }
+
+    pub fn is_reachable(&self, node_id: NodeId) -> bool {
+        self.prevs.contains_key(&node_id)
+    }
+
+    /// Iterate over the reached nodes, in increasing order of id
+    pub fn reachable(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.prevs.keys().copied()
+    }
+
+    pub fn cost_to(&self, target: NodeId) -> Option<W> {
+        self.prevs.get(&target).map(|(_, cost)| *cost)
+    }
+
+    /// Predecessor of `target` on its shortest path, `None` for the source or unreached nodes
+    pub fn prev_of(&self, target: NodeId) -> Option<NodeId> {
+        self.prevs.get(&target)?.0
+    }
+
+    /// Nodes along the shortest path from the source to `target`, both included
+    ///
+    /// `None` if `target` is unreached, or if the predecessors do not lead back to a source,
+    /// which can only happen with a tree built from an arbitrary map.
+    pub fn path_to(&self, target: NodeId) -> Option<Vec<NodeId>> {
+        let (mut prev, _) = self.prevs.get(&target)?;
+        let mut path = vec![target];
+        while let Some(cur) = prev {
+            // a path longer than the number of nodes goes around a cycle
+            if path.len() >= self.prevs.len() {
+                return None;
+            }
+            path.push(cur);
+            prev = self.prevs.get(&cur)?.0;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn as_map(&self) -> &BTreeMap<NodeId, (Option<NodeId>, W)> {
+        &self.prevs
+    }
+
+    pub fn into_map(self) -> BTreeMap<NodeId, (Option<NodeId>, W)> {
+        self.prevs
+    }
+
+    /// Subgraph of `graph` made of the edges of the tree
+    ///
+    /// Every node of `graph` is kept so that the node ids stay the same.
+    pub fn to_graph<V: NodeVal>(&self, graph: &Graph<V, W>) -> Graph<V, W> {
+        let mut tree = Graph::new();
+        tree.nodes = graph.nodes.clone();
+        for (&node_id, &(prev, cost)) in &self.prevs {
+            let Some(prev) = prev else {
+                continue;
+            };
+            let prev_cost = self.prevs[&prev].1;
+            // among parallel edges, pick the one the path actually went through
+            if let Some(edge) = graph
+                .edges(prev)
+                .iter()
+                .find(|e| e.dst() == node_id && prev_cost.add(e.cost()) == cost)
+            {
+                tree.add_edge(prev, node_id, edge.cost());
+            }
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::ShortestPathTree;
+    use crate::graph::{example_graph, Edge};
+
+    fn tree() -> ShortestPathTree<usize> {
+        let mut prevs = BTreeMap::new();
+        prevs.insert(0, (None, 0));
+        prevs.insert(1, (Some(2), 7));
+        prevs.insert(2, (Some(0), 3));
+        prevs.insert(3, (Some(1), 9));
+        prevs.insert(4, (Some(2), 5));
+        ShortestPathTree::from(prevs)
+    }
+
+    #[test]
+    fn queries() {
+        let tree = tree();
+        assert_eq!(tree.len(), 5);
+        assert_eq!(tree.path_to(3), Some(vec![0, 2, 1, 3]));
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(tree.path_to(5), None);
+        assert_eq!(tree.cost_to(4), Some(5));
+        assert_eq!(tree.cost_to(5), None);
+        assert_eq!(tree.prev_of(1), Some(2));
+        assert_eq!(tree.prev_of(0), None);
+        assert!(tree.is_reachable(4));
+        assert!(!tree.is_reachable(5));
+        assert_eq!(tree.reachable().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn malformed_map() {
+        // 1 has a predecessor missing from the map, 2 and 3 are each other's predecessor
+        let mut prevs = BTreeMap::new();
+        prevs.insert(0, (None, 0));
+        prevs.insert(1, (Some(5), 1));
+        prevs.insert(2, (Some(3), 2));
+        prevs.insert(3, (Some(2), 3));
+        prevs.insert(4, (Some(4), 4));
+        let tree = ShortestPathTree::from(prevs);
+
+        assert_eq!(tree.path_to(0), Some(vec![0]));
+        assert_eq!(tree.path_to(1), None);
+        assert_eq!(tree.path_to(2), None);
+        assert_eq!(tree.path_to(4), None);
+    }
+
+    #[test]
+    fn to_graph() {
+        // an unreached node and a costlier parallel edge, neither is part of the tree
+        let mut graph = example_graph();
+        graph.add_node(5);
+        graph.add_edge(2, 1, 6);
+
+        let sub = tree().to_graph(&graph);
+        assert_eq!(sub.nodes, graph.nodes);
+        let mut edges = sub.all_edges().copied().collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(
+            edges,
+            vec![
+                Edge::new(0, 2, 3),
+                Edge::new(1, 3, 2),
+                Edge::new(2, 1, 4),
+                Edge::new(2, 4, 2),
+            ]
+        );
+    }
+}
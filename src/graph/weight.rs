@@ -0,0 +1,213 @@
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+};
+
+/// Cost carried by the edges of a graph
+///
+/// A weight only needs a neutral element, a way to accumulate costs along a path,
+/// and a total order to compare paths against each other.
+pub trait Weight: Copy + Ord + Debug {
+    fn zero() -> Self;
+
+    fn add(self, other: Self) -> Self;
+
+    /// A value larger than any path cost, if the type has one
+    fn infinity() -> Option<Self> {
+        None
+    }
+}
+
+/// Weight that can be subtracted, needed to shift edge costs by node potentials
+pub trait SubWeight: Weight {
+    fn sub(self, other: Self) -> Self;
+}
+
+macro_rules! impl_int_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn add(self, other: Self) -> Self {
+                    self + other
+                }
+
+                fn infinity() -> Option<Self> {
+                    Some(<$t>::MAX)
+                }
+            }
+
+            impl SubWeight for $t {
+                fn sub(self, other: Self) -> Self {
+                    self - other
+                }
+            }
+        )*
+    };
+}
+
+impl_int_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Float wrapper ordered with `total_cmp`, so that floats can be used as weights
+///
+/// NaN values are ordered after positive infinity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OrdFloat<F>(pub F);
+
+macro_rules! impl_float_weight {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq for OrdFloat<$t> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.cmp(other) == Ordering::Equal
+                }
+            }
+
+            impl Eq for OrdFloat<$t> {}
+
+            impl PartialOrd for OrdFloat<$t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+
+            impl Ord for OrdFloat<$t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.total_cmp(&other.0)
+                }
+            }
+
+            impl Hash for OrdFloat<$t> {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.to_bits().hash(state);
+                }
+            }
+
+            impl From<$t> for OrdFloat<$t> {
+                fn from(val: $t) -> Self {
+                    Self(val)
+                }
+            }
+
+            impl Weight for OrdFloat<$t> {
+                fn zero() -> Self {
+                    Self(0.0)
+                }
+
+                fn add(self, other: Self) -> Self {
+                    Self(self.0 + other.0)
+                }
+
+                fn infinity() -> Option<Self> {
+                    Some(Self(<$t>::INFINITY))
+                }
+            }
+
+            impl SubWeight for OrdFloat<$t> {
+                fn sub(self, other: Self) -> Self {
+                    Self(self.0 - other.0)
+                }
+            }
+        )*
+    };
+}
+
+impl_float_weight!(f32, f64);
+
+// tuples are compared lexicographically and added component-wise
+// e.g. `(hops, latency)` first minimizes the hops, then the latency
+
+impl<A: Weight, B: Weight> Weight for (A, B) {
+    fn zero() -> Self {
+        (A::zero(), B::zero())
+    }
+
+    fn add(self, other: Self) -> Self {
+        (self.0.add(other.0), self.1.add(other.1))
+    }
+
+    fn infinity() -> Option<Self> {
+        Some((A::infinity()?, B::infinity()?))
+    }
+}
+
+impl<A: SubWeight, B: SubWeight> SubWeight for (A, B) {
+    fn sub(self, other: Self) -> Self {
+        (self.0.sub(other.0), self.1.sub(other.1))
+    }
+}
+
+impl<A: Weight, B: Weight, C: Weight> Weight for (A, B, C) {
+    fn zero() -> Self {
+        (A::zero(), B::zero(), C::zero())
+    }
+
+    fn add(self, other: Self) -> Self {
+        (
+            self.0.add(other.0),
+            self.1.add(other.1),
+            self.2.add(other.2),
+        )
+    }
+
+    fn infinity() -> Option<Self> {
+        Some((A::infinity()?, B::infinity()?, C::infinity()?))
+    }
+}
+
+impl<A: SubWeight, B: SubWeight, C: SubWeight> SubWeight for (A, B, C) {
+    fn sub(self, other: Self) -> Self {
+        (
+            self.0.sub(other.0),
+            self.1.sub(other.1),
+            self.2.sub(other.2),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OrdFloat, SubWeight, Weight};
+
+    #[test]
+    fn integers() {
+        assert_eq!(usize::zero(), 0);
+        assert_eq!(Weight::add(3usize, 4), 7);
+        assert_eq!(Weight::add(-3i64, 1), -2);
+        assert_eq!(u32::infinity(), Some(u32::MAX));
+        assert_eq!(SubWeight::sub(3i32, 5), -2);
+    }
+
+    #[test]
+    fn floats() {
+        let a = OrdFloat(1.5f64);
+        let b = OrdFloat(2.25f64);
+        assert_eq!(a.add(b), OrdFloat(3.75));
+        assert_eq!(a.sub(b), OrdFloat(-0.75));
+        assert!(a < b);
+        assert!(OrdFloat(-0.0f64) < OrdFloat(0.0));
+        assert!(OrdFloat(f32::INFINITY) < OrdFloat(f32::NAN));
+        assert_eq!(OrdFloat::<f64>::infinity(), Some(OrdFloat(f64::INFINITY)));
+
+        let mut v = vec![OrdFloat(3.0f32), OrdFloat(-1.0), OrdFloat(2.5)];
+        v.sort();
+        assert_eq!(v, vec![OrdFloat(-1.0), OrdFloat(2.5), OrdFloat(3.0)]);
+    }
+
+    #[test]
+    fn tuples() {
+        let a = (1u32, 50u64);
+        let b = (2u32, 10u64);
+        assert_eq!(a.add(b), (3, 60));
+        assert_eq!((5i32, 1i32).sub((2, 3)), (3, -2));
+        // fewer hops wins even with a larger latency
+        assert!(a < b);
+        assert_eq!(<(u8, u8)>::zero(), (0, 0));
+        assert_eq!(<(u8, u8)>::infinity(), Some((255, 255)));
+        assert_eq!(<(u8, u8, OrdFloat<f32>)>::zero(), (0, 0, OrdFloat(0.0)));
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use crate::graph::Weight;
+
+pub trait NodeVal: Ord + Copy + Default {}
+impl<T: Ord + Copy + Default> NodeVal for T {}
+
+pub type NodeId = usize;
+
+/// Directed weighted graph shared by all the graph algorithms
+#[derive(Debug, Clone)]
+pub struct Graph<V: NodeVal, W: Weight = usize> {
+    pub nodes: Vec<V>,
+    pub edges: HashMap<NodeId, Vec<Edge<W>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Edge<W: Weight = usize> {
+    src: NodeId,
+    dst: NodeId,
+    cost: W,
+}
+
+impl<W: Weight> Edge<W> {
+    pub const fn new(src: NodeId, dst: NodeId, cost: W) -> Self {
+        Self { src, dst, cost }
+    }
+
+    pub const fn src(&self) -> NodeId {
+        self.src
+    }
+
+    pub const fn dst(&self) -> NodeId {
+        self.dst
+    }
+
+    pub const fn cost(&self) -> W {
+        self.cost
+    }
+}
+
+impl<V: NodeVal, W: Weight> Default for Graph<V, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: NodeVal, W: Weight> Graph<V, W> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            edges: HashMap::new(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn add_node(&mut self, val: V) -> NodeId {
+        self.nodes.push(val);
+        self.nodes.len() - 1
+    }
+
+    /// Add a directed edge going from `src` to `dst`
+    pub fn add_edge(&mut self, src: NodeId, dst: NodeId, cost: W) {
+        self.edges
+            .entry(src)
+            .or_default()
+            .push(Edge::new(src, dst, cost));
+    }
+
+    /// Add an edge in both directions between `a` and `b`
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, cost: W) {
+        self.add_edge(a, b, cost);
+        self.add_edge(b, a, cost);
+    }
+
+    /// Remove the first edge going from `src` to `dst`, if any
+    pub fn remove_edge(&mut self, src: NodeId, dst: NodeId) -> Option<Edge<W>> {
+        let edges = self.edges.get_mut(&src)?;
+        let idx = edges.iter().position(|e| e.dst == dst)?;
+        Some(edges.remove(idx))
+    }
+
+    pub fn edges(&self, node_id: NodeId) -> &[Edge<W>] {
+        if let Some(e) = self.edges.get(&node_id) {
+            e
+        } else {
+            &[]
+        }
+    }
+
+    /// Iterate over every edge of the graph, in no particular order
+    pub fn all_edges(&self) -> impl Iterator<Item = &Edge<W>> {
+        self.edges.values().flatten()
+    }
+
+    pub fn neighbors(&self, node_id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges(node_id).iter().map(|e| e.dst)
+    }
+
+    /// Same graph with every edge flipped, the edges entering a node become the edges leaving it
+    ///
+    /// Searching the reversed graph from a node goes backwards along the original edges.
+    pub fn reversed(&self) -> Self {
+        let mut reversed = Self::new();
+        reversed.nodes = self.nodes.clone();
+        let mut sources = self.edges.keys().copied().collect::<Vec<_>>();
+        sources.sort_unstable();
+        for src in sources {
+            for edge in self.edges(src) {
+                reversed.add_edge(edge.dst, edge.src, edge.cost);
+            }
+        }
+        reversed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edge, Graph};
+
+    #[test]
+    fn construction() {
+        let mut graph = Graph::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let c = graph.add_node('c');
+        assert_eq!((a, b, c), (0, 1, 2));
+        assert_eq!(graph.node_count(), 3);
+
+        graph.add_edge(a, b, 3);
+        graph.add_undirected_edge(b, c, 5);
+
+        assert_eq!(graph.edges(a), &[Edge::new(a, b, 3)]);
+        assert_eq!(graph.neighbors(b).collect::<Vec<_>>(), vec![c]);
+        assert_eq!(graph.neighbors(c).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(graph.all_edges().count(), 3);
+    }
+
+    #[test]
+    fn remove_edge() {
+        let mut graph = Graph::new();
+        for i in 0..3 {
+            graph.add_node(i);
+        }
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 2);
+
+        assert_eq!(graph.remove_edge(0, 1), Some(Edge::new(0, 1, 1)));
+        assert_eq!(graph.remove_edge(0, 1), None);
+        assert_eq!(graph.remove_edge(2, 0), None);
+        assert_eq!(graph.neighbors(0).collect::<Vec<_>>(), vec![2]);
+
+        let edge = graph.edges(0)[0];
+        assert_eq!((edge.src(), edge.dst(), edge.cost()), (0, 2, 2));
+    }
+
+    #[test]
+    fn reversed() {
+        let mut graph = Graph::new();
+        for c in ['a', 'b', 'c'] {
+            graph.add_node(c);
+        }
+        graph.add_edge(0, 1, 4);
+        graph.add_edge(2, 1, 5);
+        graph.add_edge(1, 0, 6);
+
+        let reversed = graph.reversed();
+        assert_eq!(reversed.nodes, graph.nodes);
+        assert_eq!(reversed.edges(1), &[Edge::new(1, 0, 4), Edge::new(1, 2, 5)]);
+        assert_eq!(reversed.edges(0), &[Edge::new(0, 1, 6)]);
+        assert_eq!(reversed.edges(2), &[]);
+        assert_eq!(reversed.all_edges().count(), 3);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::data_structure::{
+    binary_heap::{heapify, sift_down},
+    MaxOrder,
+};
+
+/// In-place heap sort, O(n log n) without allocating
+///
+/// The slice is laid out as a max-heap, then the root is repeatedly swapped
+/// with the last element of the heap, which shrinks by one.
+pub fn heap_sort<T: Ord>(array: &mut [T]) {
+    heapify(array, 2, &MaxOrder);
+    sort_heap(array);
+}
+
+/// Move the `k` smallest elements, sorted, to the front of the slice, in O(n log k)
+///
+/// The order of the remaining elements is unspecified.
+pub fn partial_heap_sort<T: Ord>(array: &mut [T], k: usize) {
+    let k = k.min(array.len());
+    if k == 0 {
+        return;
+    }
+
+    // keep the k smallest elements seen so far in a max-heap,
+    // its root is the first one to be replaced by a smaller element
+    let (heap, rest) = array.split_at_mut(k);
+    heapify(heap, 2, &MaxOrder);
+    for val in rest {
+        if *val < heap[0] {
+            std::mem::swap(val, &mut heap[0]);
+            sift_down(heap, 0, 2, &MaxOrder);
+        }
+    }
+    sort_heap(heap);
+}
+
+/// Sort a slice laid out as a max-heap
+fn sort_heap<T: Ord>(heap: &mut [T]) {
+    for end in (1..heap.len()).rev() {
+        // the largest remaining element goes right after the shrunk heap
+        heap.swap(0, end);
+        sift_down(&mut heap[..end], 0, 2, &MaxOrder);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::{heap_sort, partial_heap_sort};
+
+    fn is_sorted<T: PartialOrd>(arr: &[T]) -> bool {
+        arr.windows(2).all(|w| w[0] <= w[1])
+    }
+
+    #[test]
+    fn basic() {
+        let mut res = vec![10, 8, 4, 3, 1, 9, 2, 7, 5, 6];
+        heap_sort(&mut res);
+        assert_eq!(res, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+        let mut res = vec!["a", "bb", "d", "cc"];
+        heap_sort(&mut res);
+        assert_eq!(res, vec!["a", "bb", "cc", "d"]);
+    }
+
+    #[test]
+    fn edge_cases() {
+        let mut res = Vec::<u8>::new();
+        heap_sort(&mut res);
+        assert_eq!(res, Vec::<u8>::new());
+
+        let mut res = vec![1];
+        heap_sort(&mut res);
+        assert_eq!(res, vec![1]);
+
+        let mut res = vec![4, 3, 2, 1];
+        heap_sort(&mut res);
+        assert_eq!(res, vec![1, 2, 3, 4]);
+
+        let mut res = vec![2, 2, 1, 2, 1];
+        heap_sort(&mut res);
+        assert_eq!(res, vec![1, 1, 2, 2, 2]);
+    }
+
+    #[test]
+    fn large_elements() {
+        let mut rng = rand::thread_rng();
+        let mut res = (0..100_000)
+            .map(|_| rng.gen_range(0..1_000_000))
+            .collect::<Vec<_>>();
+        heap_sort(&mut res);
+        assert!(is_sorted(&res));
+    }
+
+    #[test]
+    fn partial() {
+        let mut res = vec![10, 8, 4, 3, 1, 9, 2, 7, 5, 6];
+        partial_heap_sort(&mut res, 3);
+        assert_eq!(res[..3], [1, 2, 3]);
+
+        let mut res = vec![3, 1, 2];
+        partial_heap_sort(&mut res, 0);
+        assert_eq!(res, vec![3, 1, 2]);
+        partial_heap_sort(&mut res, 10);
+        assert_eq!(res, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn partial_random() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(0..500);
+            let k = rng.gen_range(0..=n);
+            let mut res = (0..n).map(|_| rng.gen_range(0..100)).collect::<Vec<_>>();
+            let mut expected = res.clone();
+            expected.sort();
+
+            partial_heap_sort(&mut res, k);
+            assert_eq!(res[..k], expected[..k]);
+            // the other elements are kept
+            res.sort();
+            assert_eq!(res, expected);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 mod bubble_sort;
 mod counting_sort;
+mod heap_sort;
 mod quick_sort;
 mod radix_sort;
 
 pub use bubble_sort::bubble_sort;
 pub use counting_sort::counting_sort;
+pub use heap_sort::{heap_sort, partial_heap_sort};
 pub use quick_sort::quick_sort;
 pub use radix_sort::{radix_sort_in_place, radix_sort_naive};
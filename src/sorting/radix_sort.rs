@@ -42,7 +42,8 @@ pub fn radix_sort_in_place(array: &mut [usize]) {
             digit_count[i] += digit_count[i - 1];
         }
         // place each value in the correct bucket
-        for num in array.to_vec().into_iter().rev() {
+        let snapshot = array.to_vec();
+        for num in snapshot.into_iter().rev() {
             let d = get_digit(num);
             digit_count[d] -= 1;
             array[digit_count[d]] = num;
@@ -0,0 +1,149 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::traversal::Neighbors;
+
+/// Path from `src` to `dst` with the fewest edges, searching from both ends at once
+///
+/// `reverse` lists the predecessors of a node, e.g. [`Graph::reversed`](crate::traversal::Graph::reversed),
+/// or the graph itself when its edges go both ways.
+/// The smaller frontier is expanded one whole layer at a time, and the search stops at the first node
+/// reached from both sides: no layer met before, so every path is at least as long as the one through it.
+pub fn bidirectional_bfs<N, G, R>(graph: &G, reverse: &R, src: N, dst: N) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+    R: Neighbors<N>,
+{
+    if src == dst {
+        return Some(vec![src]);
+    }
+
+    // parent of every node on the side it was reached from
+    let mut forward = HashMap::from([(src.clone(), None)]);
+    let mut backward = HashMap::from([(dst.clone(), None)]);
+    let mut forward_layer = vec![src];
+    let mut backward_layer = vec![dst];
+
+    while !forward_layer.is_empty() && !backward_layer.is_empty() {
+        let meeting = if forward_layer.len() <= backward_layer.len() {
+            expand(graph, &mut forward_layer, &mut forward, &backward)
+        } else {
+            expand(reverse, &mut backward_layer, &mut backward, &forward)
+        };
+
+        if let Some(meeting) = meeting {
+            let mut path = walk(&forward, meeting.clone());
+            path.reverse();
+            path.extend(walk(&backward, meeting).into_iter().skip(1));
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Replace `layer` by the next one, returning a node already reached by the other side
+fn expand<N, G>(
+    graph: &G,
+    layer: &mut Vec<N>,
+    parents: &mut HashMap<N, Option<N>>,
+    other: &HashMap<N, Option<N>>,
+) -> Option<N>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let mut next_layer = Vec::new();
+    for node in layer.iter() {
+        for neighbour in graph.neighbors(node) {
+            if parents.contains_key(&neighbour) {
+                continue;
+            }
+            parents.insert(neighbour.clone(), Some(node.clone()));
+            if other.contains_key(&neighbour) {
+                return Some(neighbour);
+            }
+            next_layer.push(neighbour);
+        }
+    }
+    *layer = next_layer;
+    None
+}
+
+/// Nodes from `node` up to the root of its side
+fn walk<N: Clone + Eq + Hash>(parents: &HashMap<N, Option<N>>, node: N) -> Vec<N> {
+    let mut path = vec![node];
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::bidirectional_bfs;
+    use crate::traversal::{bfs_path, Graph, Node};
+
+    fn check_path(graph: &Graph, path: &[usize], src: usize, dst: usize) {
+        assert_eq!(path.first(), Some(&src));
+        assert_eq!(path.last(), Some(&dst));
+        for pair in path.windows(2) {
+            assert!(graph.neighbours(pair[0]).contains(&pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_directed() {
+        // 0 -> 1 -> 2 -> 3 -> 4, with a shortcut 1 -> 3 and a dead end 4 -> 5
+        let nodes = (0..6).map(Node).collect();
+        let edges = vec![(0, 1), (1, 2), (2, 3), (3, 4), (1, 3), (4, 5)];
+        let graph = Graph::from_edges(nodes, edges);
+        let reverse = graph.reversed();
+
+        assert_eq!(
+            bidirectional_bfs(&graph, &reverse, 0, 4),
+            Some(vec![0, 1, 3, 4])
+        );
+        assert_eq!(bidirectional_bfs(&graph, &reverse, 2, 2), Some(vec![2]));
+        assert_eq!(bidirectional_bfs(&graph, &reverse, 4, 0), None);
+        assert_eq!(bidirectional_bfs(&graph, &reverse, 5, 1), None);
+    }
+
+    #[test]
+    fn test_undirected_grid() {
+        let grid = |&(r, c): &(i32, i32)| {
+            [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)]
+                .into_iter()
+                .filter(|&(r, c)| (0..50).contains(&r) && (0..50).contains(&c))
+        };
+        let path = bidirectional_bfs(&grid, &grid, (0, 0), (49, 30)).unwrap();
+        assert_eq!(path.len(), 49 + 30 + 1);
+        assert_eq!(bfs_path(&grid, (0, 0), (49, 30)).unwrap().len(), path.len());
+        // a node outside the grid has no edges
+        assert_eq!(bidirectional_bfs(&grid, &grid, (0, 0), (50, 50)), None);
+    }
+
+    #[test]
+    fn test_random_against_bfs() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let n = rng.gen_range(2..60);
+            let edges = (0..rng.gen_range(0..n * 3))
+                .map(|_| (rng.gen_range(0..n), rng.gen_range(0..n)))
+                .collect();
+            let graph = Graph::from_edges((0..n).map(Node).collect(), edges);
+            let reverse = graph.reversed();
+
+            for _ in 0..20 {
+                let (src, dst) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                let expected = bfs_path(&graph, src, dst);
+                let path = bidirectional_bfs(&graph, &reverse, src, dst);
+                assert_eq!(path.as_ref().map(Vec::len), expected.as_ref().map(Vec::len));
+                if let Some(path) = path {
+                    check_path(&graph, &path, src, dst);
+                }
+            }
+        }
+    }
+}
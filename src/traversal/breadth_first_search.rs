@@ -1,56 +1,190 @@
-use std::collections::{HashSet, VecDeque};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
 
-pub struct Node(usize);
-pub type NodeId = usize;
+use crate::traversal::Neighbors;
 
-pub type Edge = (NodeId, NodeId);
+/// Breadth First Search
+///
+/// Returns the nodes in the order they were visited, and the first node satisfying `goal`.
+pub fn breadth_first_search<N, G>(
+    graph: &G,
+    root: N,
+    goal: impl Fn(&N) -> bool,
+) -> (Vec<N>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let mut path = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(root.clone());
+    queue.push_back(root);
+
+    while let Some(node) = queue.pop_front() {
+        path.push(node.clone());
+        if goal(&node) {
+            return (path, Some(node));
+        }
 
-pub struct Graph {
-    pub nodes: Vec<Node>,
-    pub edges: Vec<Edge>,
+        for neighbour in graph.neighbors(&node) {
+            if !visited.contains(&neighbour) {
+                visited.insert(neighbour.clone());
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    (path, None)
 }
 
-impl Graph {
-    pub fn neighbours(&self, node_id: NodeId) -> Vec<NodeId> {
-        self.edges
-            .iter()
-            .filter(|(from, _)| *from == node_id)
-            .map(|(_, to)| *to)
-            .collect()
+/// Breadth first tree, giving the fewest hops from the root to every reachable node
+pub struct BfsTree<N> {
+    root: N,
+    // hop distance and parent of every reached node
+    nodes: HashMap<N, (usize, Option<N>)>,
+}
+
+impl<N: Clone + Eq + Hash> BfsTree<N> {
+    pub fn root(&self) -> &N {
+        &self.root
+    }
+
+    /// Number of nodes reachable from the root, the root included
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    /// Number of edges on a shortest path from the root, `None` if `node` is unreachable
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.nodes.get(node).map(|(distance, _)| *distance)
+    }
+
+    /// Previous node on a shortest path from the root, `None` for the root and unreachable nodes
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.nodes.get(node)?.1.as_ref()
+    }
+
+    /// Shortest path from the root to `node`, both included
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        path_from_parents(&self.nodes, node.clone())
     }
 }
 
-/// Breadth First Search
-pub fn breadth_first_search(
-    graph: &Graph,
-    root_id: NodeId,
-    target: &Node,
-) -> (Vec<NodeId>, Option<NodeId>) {
-    let mut path = Vec::new();
+/// Explore everything reachable from `root`, recording the distance and parent of every node
+pub fn bfs_tree<N, G>(graph: &G, root: N) -> BfsTree<N>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let nodes = explore(graph, root.clone(), |_| false);
+    BfsTree { root, nodes }
+}
+
+/// Nodes reachable from `root` grouped by their distance to it, in the order they were visited
+pub fn bfs_layers<N, G>(graph: &G, root: N) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
     let mut visited = HashSet::new();
-    let mut queue = VecDeque::new();
-    queue.push_back(root_id);
-    visited.insert(root_id);
-
-    while let Some(node_id) = queue.pop_front() {
-        let node = &graph.nodes[node_id];
-        path.push(node.0);
-        if node.0 == target.0 {
-            return (path, Some(node_id));
+    visited.insert(root.clone());
+    let mut layers = Vec::new();
+    let mut layer = vec![root];
+
+    while !layer.is_empty() {
+        let mut next_layer = Vec::new();
+        for node in &layer {
+            for neighbour in graph.neighbors(node) {
+                if visited.insert(neighbour.clone()) {
+                    next_layer.push(neighbour);
+                }
+            }
         }
+        layers.push(layer);
+        layer = next_layer;
+    }
+    layers
+}
+
+/// Path from `src` to `dst` with the fewest edges, both ends included
+///
+/// The search stops as soon as `dst` is discovered.
+pub fn bfs_path<N, G>(graph: &G, src: N, dst: N) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let nodes = explore(graph, src, |node| *node == dst);
+    path_from_parents(&nodes, dst)
+}
+
+/// Breadth first search recording the distance and parent of every discovered node,
+/// until a node satisfying `stop` is discovered
+fn explore<N, G>(graph: &G, root: N, stop: impl Fn(&N) -> bool) -> HashMap<N, (usize, Option<N>)>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    let mut nodes = HashMap::new();
+    let mut queue = VecDeque::new();
+    nodes.insert(root.clone(), (0, None));
+    if stop(&root) {
+        return nodes;
+    }
+    queue.push_back(root);
 
-        for neighbour_id in graph.neighbours(node_id) {
-            if visited.insert(neighbour_id) {
-                queue.push_back(neighbour_id);
+    while let Some(node) = queue.pop_front() {
+        let distance = nodes[&node].0;
+        for neighbour in graph.neighbors(&node) {
+            if nodes.contains_key(&neighbour) {
+                continue;
+            }
+            nodes.insert(neighbour.clone(), (distance + 1, Some(node.clone())));
+            if stop(&neighbour) {
+                return nodes;
             }
+            queue.push_back(neighbour);
         }
     }
-    (path, None)
+    nodes
+}
+
+fn path_from_parents<N: Clone + Eq + Hash>(
+    nodes: &HashMap<N, (usize, Option<N>)>,
+    node: N,
+) -> Option<Vec<N>> {
+    let (distance, mut parent) = nodes.get(&node)?.clone();
+    let mut path = Vec::with_capacity(distance + 1);
+    path.push(node);
+    while let Some(node) = parent {
+        parent = nodes[&node].1.clone();
+        path.push(node);
+    }
+    path.reverse();
+    Some(path)
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{breadth_first_search, Graph, Node};
+    use super::{bfs_layers, bfs_path, bfs_tree, breadth_first_search};
+    use crate::{
+        graph,
+        traversal::{Graph, Node},
+    };
+
+    fn values(graph: &Graph, path: &[usize]) -> Vec<usize> {
+        path.iter().map(|&id| graph.node(id).0).collect()
+    }
 
     /* Example graph #1:
      *
@@ -66,10 +200,7 @@ mod tests {
         let nodes = vec![1, 2, 3, 4, 5, 6, 7, 8];
         let edges = vec![(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (2, 6), (4, 7)];
 
-        Graph {
-            nodes: nodes.into_iter().map(Node).collect(),
-            edges,
-        }
+        Graph::from_edges(nodes.into_iter().map(Node).collect(), edges)
     }
 
     /* Example graph #2:
@@ -99,19 +230,15 @@ mod tests {
             (6, 5),
         ];
 
-        Graph {
-            nodes: nodes.into_iter().map(Node).collect(),
-            edges,
-        }
+        Graph::from_edges(nodes.into_iter().map(Node).collect(), edges)
     }
 
     #[test]
     fn bfs_fail1() {
         let graph = graph1();
         let root_id = 0;
-        let target = Node(10);
 
-        let (_, found) = breadth_first_search(&graph, root_id, &target);
+        let (_, found) = breadth_first_search(&graph, root_id, |&id| graph.node(id).0 == 10);
         assert_eq!(found, None);
     }
 
@@ -120,13 +247,12 @@ mod tests {
         let graph = graph1();
 
         let root_id = 0;
-        let target = Node(8);
         let expected_path = vec![1, 2, 3, 4, 5, 6, 7, 8];
 
-        let (path, found) = breadth_first_search(&graph, root_id, &target);
+        let (path, found) = breadth_first_search(&graph, root_id, |&id| graph.node(id).0 == 8);
 
         assert_eq!(found, Some(7));
-        assert_eq!(path, expected_path);
+        assert_eq!(values(&graph, &path), expected_path);
     }
 
     #[test]
@@ -134,9 +260,8 @@ mod tests {
         let graph = graph2();
 
         let root_id = 0;
-        let target = Node(8);
 
-        let (_, found) = breadth_first_search(&graph, root_id, &target);
+        let (_, found) = breadth_first_search(&graph, root_id, |&id| graph.node(id).0 == 8);
         assert_eq!(found, None);
     }
 
@@ -145,11 +270,124 @@ mod tests {
         let graph = graph2();
 
         let root_id = 3;
-        let target = Node(1);
         let expected_path = vec![4, 3, 7, 6, 2, 1];
 
-        let (path, found) = breadth_first_search(&graph, root_id, &target);
+        let (path, found) = breadth_first_search(&graph, root_id, |&id| graph.node(id).0 == 1);
         assert_eq!(found, Some(0));
-        assert_eq!(path, expected_path);
+        assert_eq!(values(&graph, &path), expected_path);
+    }
+
+    #[test]
+    fn bfs_large() {
+        // a chain of 100k nodes, each also linked to 10 earlier nodes: 1M edges
+        let n = 100_000;
+        let mut edges = Vec::with_capacity(n * 11);
+        for i in 0..n {
+            for k in 1..=10 {
+                edges.push((i, i.saturating_sub(k * 7)));
+            }
+            if i + 1 < n {
+                edges.push((i, i + 1));
+            }
+        }
+        let graph = Graph::from_edges((0..n).map(Node).collect(), edges);
+
+        let (path, found) = breadth_first_search(&graph, 0, |&id| id == n - 1);
+        assert_eq!(found, Some(n - 1));
+        assert_eq!(path.len(), n);
+    }
+
+    #[test]
+    fn bfs_weighted_graph() {
+        let mut graph = graph::Graph::<char>::new();
+        for c in ['a', 'b', 'c', 'd'] {
+            graph.add_node(c);
+        }
+        graph.add_edge(0, 1, 10);
+        graph.add_edge(0, 2, 1);
+        graph.add_edge(2, 3, 1);
+
+        let (path, found) = breadth_first_search(&graph, 0, |&id| graph.nodes[id] == 'd');
+        assert_eq!(found, Some(3));
+        assert_eq!(path, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn bfs_implicit_graph() {
+        // fewest operations to go from 1 to 100, where an operation is either +1 or *3
+        let next = |&(n, steps): &(u32, u32)| {
+            [n + 1, n * 3]
+                .into_iter()
+                .filter(|&m| m <= 100)
+                .map(move |m| (m, steps + 1))
+        };
+        // the step count makes every state unique, so the search is a tree search
+        let (_, found) = breadth_first_search(&next, (1, 0), |&(n, _)| n == 100);
+        // 1 -> 3 -> 9 -> 10 -> 11 -> 33 -> 99 -> 100
+        assert_eq!(found, Some((100, 7)));
+    }
+
+    #[test]
+    fn bfs_tree_distances() {
+        let graph = graph2();
+        let tree = bfs_tree(&graph, 0);
+
+        assert_eq!(tree.root(), &0);
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.distance(&0), Some(0));
+        assert_eq!(tree.distance(&5), Some(2));
+        assert_eq!(tree.distance(&3), Some(4));
+        assert_eq!(tree.distance(&7), None);
+        assert_eq!(tree.parent(&0), None);
+        assert_eq!(tree.parent(&4), Some(&1));
+        assert_eq!(tree.parent(&7), None);
+        assert_eq!(
+            values(&graph, &tree.path_to(&3).unwrap()),
+            vec![1, 2, 6, 3, 4]
+        );
+        assert_eq!(tree.path_to(&7), None);
+    }
+
+    #[test]
+    fn bfs_layers_by_depth() {
+        let graph = graph1();
+        let layers = bfs_layers(&graph, 0)
+            .iter()
+            .map(|layer| values(&graph, layer))
+            .collect::<Vec<_>>();
+        assert_eq!(layers, vec![vec![1], vec![2, 3], vec![4, 5, 6, 7], vec![8]]);
+
+        let graph = graph2();
+        let layers = bfs_layers(&graph, 7);
+        assert_eq!(layers, vec![vec![7]]);
+    }
+
+    #[test]
+    fn bfs_path_shortest() {
+        let graph = graph2();
+        let path = bfs_path(&graph, 4, 3).unwrap();
+        assert_eq!(values(&graph, &path), vec![5, 2, 6, 3, 4]);
+        assert_eq!(bfs_path(&graph, 2, 2), Some(vec![2]));
+        assert_eq!(bfs_path(&graph, 0, 7), None);
+
+        // directed edges are only followed forwards
+        let graph = graph1();
+        assert_eq!(bfs_path(&graph, 7, 0), None);
+        assert_eq!(
+            values(&graph, &bfs_path(&graph, 0, 7).unwrap()),
+            vec![1, 2, 5, 8]
+        );
+    }
+
+    #[test]
+    fn bfs_path_implicit_graph() {
+        // same puzzle as above, the path gives the operations
+        let next = |&n: &u32| [n + 1, n * 3].into_iter().filter(|&m| m <= 100);
+        let path = bfs_path(&next, 1, 100).unwrap();
+        assert_eq!(path.len(), 8);
+        assert_eq!(bfs_tree(&next, 1).distance(&100), Some(7));
+        for pair in path.windows(2) {
+            assert!(pair[1] == pair[0] + 1 || pair[1] == pair[0] * 3);
+        }
     }
 }
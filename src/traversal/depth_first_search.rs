@@ -1,42 +1,30 @@
-use std::collections::{HashSet, VecDeque};
+use std::{
+    collections::{HashSet, VecDeque},
+    hash::Hash,
+};
 
-pub struct Node(usize);
-pub type NodeId = usize;
-
-pub type Edge = (NodeId, NodeId);
-
-pub struct Graph {
-    pub nodes: Vec<Node>,
-    pub edges: Vec<Edge>,
-}
-
-impl Graph {
-    pub fn neighbours(&self, node: NodeId) -> Vec<NodeId> {
-        self.edges
-            .iter()
-            .filter(|(from, _)| *from == node)
-            .map(|(_, to)| *to)
-            .collect()
-    }
-}
+use crate::traversal::Neighbors;
 
 /// Naive Recursive Depth First Search
 /// This doesn't support bidirectional nodes (infinite recursion)
 /// It is best suited for traversing tree-like structures
-pub fn depth_first_search_rec(
-    graph: &Graph,
-    path: &mut Vec<NodeId>,
-    cur_node_id: NodeId,
-    target: &Node,
-) -> Option<NodeId> {
-    let cur_node = &graph.nodes[cur_node_id];
-    path.push(cur_node.0);
-    if cur_node.0 == target.0 {
-        return Some(cur_node_id);
+pub fn depth_first_search_rec<N, G>(
+    graph: &G,
+    path: &mut Vec<N>,
+    cur_node: N,
+    goal: &impl Fn(&N) -> bool,
+) -> Option<N>
+where
+    N: Clone,
+    G: Neighbors<N>,
+{
+    path.push(cur_node.clone());
+    if goal(&cur_node) {
+        return Some(cur_node);
     }
 
-    for neighbour in graph.neighbours(cur_node_id) {
-        if let Some(v) = depth_first_search_rec(graph, path, neighbour, target) {
+    for neighbour in graph.neighbors(&cur_node) {
+        if let Some(v) = depth_first_search_rec(graph, path, neighbour, goal) {
             return Some(v);
         }
     }
@@ -44,26 +32,34 @@ pub fn depth_first_search_rec(
 }
 
 /// Iterative Depth First Search
-pub fn depth_first_search_it(
-    graph: &Graph,
-    root_id: NodeId,
-    target: &Node,
-) -> (Vec<NodeId>, Option<NodeId>) {
+///
+/// Returns the nodes in the order they were visited, and the first node satisfying `goal`.
+pub fn depth_first_search_it<N, G>(
+    graph: &G,
+    root: N,
+    goal: impl Fn(&N) -> bool,
+) -> (Vec<N>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
     let mut path = Vec::new();
     let mut visited = HashSet::new();
     let mut queue = VecDeque::new();
-    queue.push_back(root_id);
-    visited.insert(root_id);
-
-    while let Some(node_id) = queue.pop_front() {
-        let node = &graph.nodes[node_id];
-        path.push(node.0);
-        if node.0 == target.0 {
-            return (path, Some(node_id));
+    visited.insert(root.clone());
+    queue.push_back(root);
+
+    while let Some(node) = queue.pop_front() {
+        path.push(node.clone());
+        if goal(&node) {
+            return (path, Some(node));
         }
 
-        for neighbour in graph.neighbours(node_id).into_iter().rev() {
-            if visited.insert(neighbour) {
+        // push the neighbours in reverse so that the first one is visited first
+        let neighbours = graph.neighbors(&node).collect::<Vec<_>>();
+        for neighbour in neighbours.into_iter().rev() {
+            if !visited.contains(&neighbour) {
+                visited.insert(neighbour.clone());
                 queue.push_front(neighbour);
             }
         }
@@ -73,7 +69,12 @@ pub fn depth_first_search_it(
 
 #[cfg(test)]
 mod tests {
-    use super::{depth_first_search_it, depth_first_search_rec, Graph, Node};
+    use super::{depth_first_search_it, depth_first_search_rec};
+    use crate::traversal::{Graph, Node};
+
+    fn values(graph: &Graph, path: &[usize]) -> Vec<usize> {
+        path.iter().map(|&id| graph.node(id).0).collect()
+    }
 
     /* Example graph #1:
      *
@@ -89,10 +90,7 @@ mod tests {
         let nodes = vec![1, 2, 3, 4, 5, 6, 7, 8];
         let edges = vec![(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (2, 6), (4, 7)];
 
-        Graph {
-            nodes: nodes.into_iter().map(Node).collect(),
-            edges,
-        }
+        Graph::from_edges(nodes.into_iter().map(Node).collect(), edges)
     }
 
     /* Example graph #2:
@@ -122,10 +120,7 @@ mod tests {
             (6, 5),
         ];
 
-        Graph {
-            nodes: nodes.into_iter().map(Node).collect(),
-            edges,
-        }
+        Graph::from_edges(nodes.into_iter().map(Node).collect(), edges)
     }
 
     #[test]
@@ -133,13 +128,13 @@ mod tests {
         let graph = graph1();
 
         let root_id = 0;
-        let target = Node(10);
+        let target = |&id: &usize| graph.node(id).0 == 10;
         let correct_path = vec![1, 2, 4, 5, 8, 3, 6, 7];
 
         let mut path = Vec::new();
         let res = depth_first_search_rec(&graph, &mut path, root_id, &target);
         assert_eq!(res, None);
-        assert_eq!(correct_path, path);
+        assert_eq!(correct_path, values(&graph, &path));
     }
 
     #[test]
@@ -147,13 +142,13 @@ mod tests {
         let graph = graph1();
 
         let root_id = 0;
-        let target = Node(6);
+        let target = |&id: &usize| graph.node(id).0 == 6;
         let correct_path = vec![1, 2, 4, 5, 8, 3, 6];
 
         let mut path = Vec::new();
         let res = depth_first_search_rec(&graph, &mut path, root_id, &target);
         assert_eq!(res, Some(5));
-        assert_eq!(correct_path, path);
+        assert_eq!(correct_path, values(&graph, &path));
     }
 
     #[test]
@@ -161,11 +156,11 @@ mod tests {
         let graph = graph2();
 
         let root_id = 0;
-        let target = Node(4);
+        let target = |&id: &usize| graph.node(id).0 == 4;
         let correct_path = vec![1, 2, 5, 6, 3, 4];
 
-        let (path, res) = depth_first_search_it(&graph, root_id, &target);
-        assert_eq!(correct_path, path);
+        let (path, res) = depth_first_search_it(&graph, root_id, target);
+        assert_eq!(correct_path, values(&graph, &path));
         assert_eq!(res, Some(3));
     }
 
@@ -174,11 +169,33 @@ mod tests {
         let graph = graph2();
 
         let root_id = 0;
-        let target = Node(8);
+        let target = |&id: &usize| graph.node(id).0 == 8;
         let correct_path = vec![1, 2, 5, 6, 3, 4, 7];
 
-        let (path, res) = depth_first_search_it(&graph, root_id, &target);
-        assert_eq!(correct_path, path);
+        let (path, res) = depth_first_search_it(&graph, root_id, target);
+        assert_eq!(correct_path, values(&graph, &path));
         assert_eq!(res, None);
     }
+
+    #[test]
+    fn dfs_implicit_graph() {
+        // binary strings of length 3 in lexicographic order, as the leaves of an implicit tree
+        let children = |s: &String| {
+            let s = s.clone();
+            (s.len() < 3)
+                .then(|| ["0", "1"].map(|bit| s.clone() + bit))
+                .into_iter()
+                .flatten()
+        };
+        let mut path = Vec::new();
+        let found = depth_first_search_rec(&children, &mut path, String::new(), &|s| s == "101");
+        assert_eq!(found.as_deref(), Some("101"));
+        let leaves = path.iter().filter(|s| s.len() == 3).collect::<Vec<_>>();
+        assert_eq!(leaves, ["000", "001", "010", "011", "100", "101"]);
+
+        let (path, found) = depth_first_search_it(&children, String::new(), |s| s == "11");
+        assert_eq!(found.as_deref(), Some("11"));
+        // every string before "11" in preorder, including the empty root
+        assert_eq!(path.len(), 13);
+    }
 }
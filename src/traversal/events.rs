@@ -0,0 +1,285 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+use crate::traversal::Neighbors;
+
+/// Step of a graph traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalEvent<N> {
+    /// The node is reached for the first time
+    Discover(N),
+    /// The edge discovers its target, it is part of the traversal tree
+    TreeEdge(N, N),
+    /// The edge goes back to an ancestor in the traversal tree, it closes a cycle
+    BackEdge(N, N),
+    /// Any other edge, to a descendant already discovered or to another branch of the tree
+    ForwardOrCrossEdge(N, N),
+    /// All the edges of the node were examined
+    Finish(N),
+}
+
+/// Lazy depth first traversal, yielding an event at every step
+///
+/// The order of the events gives the discovery and finish times of the nodes,
+/// and the edges are classified on the fly from the state of their target:
+/// undiscovered for a tree edge, discovered but not finished for a back edge, finished otherwise.
+pub struct Dfs<'a, N, G> {
+    graph: &'a G,
+    // nodes being explored, with their edges left to examine
+    stack: Vec<(N, std::vec::IntoIter<N>)>,
+    // whether every discovered node is finished
+    finished: HashMap<N, bool>,
+    pending: Option<TraversalEvent<N>>,
+}
+
+impl<'a, N, G> Dfs<'a, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    pub fn new(graph: &'a G, root: N) -> Self {
+        let mut dfs = Self {
+            graph,
+            stack: Vec::new(),
+            finished: HashMap::new(),
+            pending: None,
+        };
+        dfs.discover(root.clone());
+        dfs.pending = Some(TraversalEvent::Discover(root));
+        dfs
+    }
+
+    fn discover(&mut self, node: N) {
+        let neighbors = self.graph.neighbors(&node).collect::<Vec<_>>();
+        self.finished.insert(node.clone(), false);
+        self.stack.push((node, neighbors.into_iter()));
+    }
+}
+
+impl<N, G> Iterator for Dfs<'_, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    type Item = TraversalEvent<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        let (node, edges) = self.stack.last_mut()?;
+        let node = node.clone();
+        let Some(next) = edges.next() else {
+            self.stack.pop();
+            self.finished.insert(node.clone(), true);
+            return Some(TraversalEvent::Finish(node));
+        };
+
+        Some(match self.finished.get(&next) {
+            None => {
+                self.discover(next.clone());
+                self.pending = Some(TraversalEvent::Discover(next.clone()));
+                TraversalEvent::TreeEdge(node, next)
+            }
+            Some(false) => TraversalEvent::BackEdge(node, next),
+            Some(true) => TraversalEvent::ForwardOrCrossEdge(node, next),
+        })
+    }
+}
+
+/// Lazy breadth first traversal, yielding an event at every step
+///
+/// All the edges of a node are examined at once, when the node is taken out of the queue.
+/// Classifying a non-tree edge walks up the traversal tree, which costs the depth difference of its ends.
+pub struct Bfs<'a, N, G> {
+    graph: &'a G,
+    queue: VecDeque<N>,
+    // parent and depth of every discovered node
+    tree: HashMap<N, (Option<N>, usize)>,
+    pending: VecDeque<TraversalEvent<N>>,
+}
+
+impl<'a, N, G> Bfs<'a, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    pub fn new(graph: &'a G, root: N) -> Self {
+        let mut tree = HashMap::new();
+        tree.insert(root.clone(), (None, 0));
+        Self {
+            graph,
+            queue: VecDeque::from([root.clone()]),
+            tree,
+            pending: VecDeque::from([TraversalEvent::Discover(root)]),
+        }
+    }
+
+    /// Whether `ancestor` is on the path from the root to `node`, `node` included
+    fn is_ancestor(&self, ancestor: &N, node: &N) -> bool {
+        let depth = self.tree[ancestor].1;
+        let mut node = node;
+        loop {
+            let (parent, node_depth) = &self.tree[node];
+            if *node_depth <= depth {
+                return node == ancestor;
+            }
+            match parent {
+                Some(parent) => node = parent,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl<N, G> Iterator for Bfs<'_, N, G>
+where
+    N: Clone + Eq + Hash,
+    G: Neighbors<N>,
+{
+    type Item = TraversalEvent<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+
+        let node = self.queue.pop_front()?;
+        let depth = self.tree[&node].1;
+        for next in self.graph.neighbors(&node) {
+            if !self.tree.contains_key(&next) {
+                self.tree
+                    .insert(next.clone(), (Some(node.clone()), depth + 1));
+                self.pending
+                    .push_back(TraversalEvent::TreeEdge(node.clone(), next.clone()));
+                self.pending
+                    .push_back(TraversalEvent::Discover(next.clone()));
+                self.queue.push_back(next);
+            } else if self.is_ancestor(&next, &node) {
+                self.pending
+                    .push_back(TraversalEvent::BackEdge(node.clone(), next));
+            } else {
+                self.pending
+                    .push_back(TraversalEvent::ForwardOrCrossEdge(node.clone(), next));
+            }
+        }
+        self.pending.push_back(TraversalEvent::Finish(node));
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bfs, Dfs, TraversalEvent};
+    use crate::traversal::{breadth_first_search, Graph, Node};
+
+    use TraversalEvent::*;
+
+    /*  0 -> 1 -> 2 -> 0 (cycle)
+     *  0 -> 2         (forward edge)
+     *  3 -> 1         (cross edge)
+     *  2 -> 4
+     */
+    fn graph() -> Graph {
+        let nodes = (0..5).map(Node).collect();
+        let edges = vec![(0, 1), (0, 2), (1, 2), (2, 0), (2, 4), (3, 1)];
+        Graph::from_edges(nodes, edges)
+    }
+
+    #[test]
+    fn test_dfs_events() {
+        let graph = graph();
+        let events = Dfs::new(&graph, 0).collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                Discover(0),
+                TreeEdge(0, 1),
+                Discover(1),
+                TreeEdge(1, 2),
+                Discover(2),
+                BackEdge(2, 0),
+                TreeEdge(2, 4),
+                Discover(4),
+                Finish(4),
+                Finish(2),
+                Finish(1),
+                ForwardOrCrossEdge(0, 2),
+                Finish(0),
+            ]
+        );
+
+        let events = Dfs::new(&graph, 3).collect::<Vec<_>>();
+        assert_eq!(events.len(), 16);
+        assert!(events.contains(&Discover(3)));
+        assert_eq!(events.last(), Some(&Finish(3)));
+    }
+
+    #[test]
+    fn test_dfs_topological_order() {
+        // reversed finish order of an acyclic graph
+        let dag = |&n: &u32| (1..=3).map(move |k| n * 2 + k).filter(|&m| m < 12);
+        let mut order = Vec::new();
+        for event in Dfs::new(&dag, 0) {
+            match event {
+                BackEdge(..) => panic!("the graph is acyclic"),
+                Finish(n) => order.push(n),
+                _ => {}
+            }
+        }
+        order.reverse();
+        for (i, &n) in order.iter().enumerate() {
+            for m in dag(&n) {
+                assert!(order[i..].contains(&m));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bfs_events() {
+        let graph = graph();
+        let events = Bfs::new(&graph, 0).collect::<Vec<_>>();
+        assert_eq!(
+            events,
+            vec![
+                Discover(0),
+                TreeEdge(0, 1),
+                Discover(1),
+                TreeEdge(0, 2),
+                Discover(2),
+                Finish(0),
+                ForwardOrCrossEdge(1, 2),
+                Finish(1),
+                BackEdge(2, 0),
+                TreeEdge(2, 4),
+                Discover(4),
+                Finish(2),
+                Finish(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bfs_matches_search() {
+        let grid = |&(r, c): &(i32, i32)| {
+            [(r + 1, c), (r, c + 1), (r - 1, c), (r, c - 1)]
+                .into_iter()
+                .filter(|&(r, c)| (0..20).contains(&r) && (0..20).contains(&c))
+        };
+        let discovered = Bfs::new(&grid, (0, 0))
+            .filter_map(|event| match event {
+                Discover(n) => Some(n),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let (visited, _) = breadth_first_search(&grid, (0, 0), |_| false);
+        assert_eq!(discovered, visited);
+
+        // early termination, the rest of the grid is never explored
+        let first = Bfs::new(&grid, (0, 0)).find(|event| matches!(event, Discover((5, 5))));
+        assert_eq!(first, Some(Discover((5, 5))));
+    }
+}
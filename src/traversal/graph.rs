@@ -0,0 +1,175 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Node(pub usize);
+pub type NodeId = usize;
+
+pub type Edge = (NodeId, NodeId);
+
+/// Directed graph stored in compressed sparse row form
+///
+/// The targets of all the edges are laid out in a single array, grouped by source node,
+/// so the neighbours of a node are a contiguous slice found in O(1).
+pub struct Graph {
+    // only grown through the builder, which lays out the edges of every node
+    nodes: Vec<Node>,
+    // the neighbours of node `i` are `targets[offsets[i]..offsets[i + 1]]`
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+}
+
+impl Graph {
+    /// Build a graph from an edge list, see [`GraphBuilder`]
+    pub fn from_edges(nodes: Vec<Node>, edges: Vec<Edge>) -> Self {
+        GraphBuilder { nodes, edges }.build()
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `node_id` is not a node of the graph.
+    pub fn node(&self, node_id: NodeId) -> &Node {
+        &self.nodes[node_id]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Targets of the edges leaving `node_id`, in the order the edges were added
+    pub fn neighbours(&self, node_id: NodeId) -> &[NodeId] {
+        &self.targets[self.offsets[node_id]..self.offsets[node_id + 1]]
+    }
+
+    /// Same graph with every edge flipped, to walk the original edges backwards
+    pub fn reversed(&self) -> Self {
+        let mut edges = Vec::with_capacity(self.edge_count());
+        for from in 0..self.node_count() {
+            edges.extend(self.neighbours(from).iter().map(|&to| (to, from)));
+        }
+        Self::from_edges(self.nodes.clone(), edges)
+    }
+}
+
+/// Collects nodes and edges before laying them out as a [`Graph`]
+#[derive(Default)]
+pub struct GraphBuilder {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_node(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges.push((from, to));
+    }
+
+    /// Add both directions of an edge
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId) {
+        self.add_edge(a, b);
+        self.add_edge(b, a);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if an edge refers to a node that was not added.
+    pub fn build(self) -> Graph {
+        let node_count = self.nodes.len();
+
+        // count the edges leaving every node, then turn the counts into offsets
+        let mut offsets = vec![0; node_count + 1];
+        for &(from, to) in &self.edges {
+            assert!(
+                from < node_count && to < node_count,
+                "edge ({from}, {to}) refers to a missing node"
+            );
+            offsets[from + 1] += 1;
+        }
+        for i in 0..node_count {
+            offsets[i + 1] += offsets[i];
+        }
+
+        // place every target at the next free slot of its source,
+        // which keeps the edges of a node in insertion order
+        let mut next = offsets.clone();
+        let mut targets = vec![0; self.edges.len()];
+        for (from, to) in self.edges {
+            targets[next[from]] = to;
+            next[from] += 1;
+        }
+
+        Graph {
+            nodes: self.nodes,
+            offsets,
+            targets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Graph, GraphBuilder, Node};
+
+    #[test]
+    fn test_neighbours_order() {
+        let nodes = (0..4).map(Node).collect();
+        let edges = vec![(2, 0), (0, 3), (2, 1), (0, 1), (2, 3), (0, 2)];
+        let graph = Graph::from_edges(nodes, edges);
+
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 6);
+        assert_eq!(graph.neighbours(0), &[3, 1, 2]);
+        assert_eq!(graph.neighbours(1), &[] as &[usize]);
+        assert_eq!(graph.neighbours(2), &[0, 1, 3]);
+        assert_eq!(graph.neighbours(3), &[] as &[usize]);
+    }
+
+    #[test]
+    fn test_builder() {
+        let mut builder = GraphBuilder::new();
+        let a = builder.add_node(Node(10));
+        let b = builder.add_node(Node(20));
+        let c = builder.add_node(Node(30));
+        builder.add_undirected_edge(a, b);
+        builder.add_edge(c, a);
+        let graph = builder.build();
+
+        assert_eq!(graph.node(c).0, 30);
+        assert_eq!(graph.nodes(), &[Node(10), Node(20), Node(30)]);
+        assert_eq!(graph.neighbours(a), &[b]);
+        assert_eq!(graph.neighbours(b), &[a]);
+        assert_eq!(graph.neighbours(c), &[a]);
+    }
+
+    #[test]
+    fn test_reversed() {
+        let nodes = (0..4).map(Node).collect();
+        let edges = vec![(2, 0), (0, 3), (2, 1), (0, 1), (2, 3)];
+        let reversed = Graph::from_edges(nodes, edges).reversed();
+
+        assert_eq!(reversed.edge_count(), 5);
+        assert_eq!(reversed.neighbours(0), &[2]);
+        assert_eq!(reversed.neighbours(1), &[0, 2]);
+        assert_eq!(reversed.neighbours(2), &[] as &[usize]);
+        assert_eq!(reversed.neighbours(3), &[0, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_missing_node() {
+        Graph::from_edges(vec![Node(0)], vec![(0, 1)]);
+    }
+}
@@ -1,7 +1,16 @@
+mod bidirectional_bfs;
 mod breadth_first_search;
 mod depth_first_search;
+mod events;
+mod graph;
+mod neighbors;
 
 pub use depth_first_search::depth_first_search_it;
 pub use depth_first_search::depth_first_search_rec;
 
-pub use breadth_first_search::breadth_first_search;
+pub use bidirectional_bfs::bidirectional_bfs;
+pub use breadth_first_search::{bfs_layers, bfs_path, bfs_tree, breadth_first_search, BfsTree};
+
+pub use events::{Bfs, Dfs, TraversalEvent};
+pub use graph::{Edge, Graph, GraphBuilder, Node, NodeId};
+pub use neighbors::Neighbors;
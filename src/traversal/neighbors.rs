@@ -0,0 +1,31 @@
+use crate::{graph, graph::NodeVal, graph::Weight, traversal::Graph};
+
+/// Anything that can list the successors of a node, so that it can be traversed
+///
+/// Besides the graph types, any closure `Fn(&N) -> impl IntoIterator<Item = N>` is a graph,
+/// which allows searching implicit state spaces without building them first.
+pub trait Neighbors<N> {
+    fn neighbors(&self, node: &N) -> impl Iterator<Item = N>;
+}
+
+impl Neighbors<usize> for Graph {
+    fn neighbors(&self, node: &usize) -> impl Iterator<Item = usize> {
+        self.neighbours(*node).iter().copied()
+    }
+}
+
+impl<V: NodeVal, W: Weight> Neighbors<graph::NodeId> for graph::Graph<V, W> {
+    fn neighbors(&self, node: &graph::NodeId) -> impl Iterator<Item = graph::NodeId> {
+        graph::Graph::neighbors(self, *node)
+    }
+}
+
+impl<N, F, I> Neighbors<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    fn neighbors(&self, node: &N) -> impl Iterator<Item = N> {
+        self(node).into_iter()
+    }
+}